@@ -1,4 +1,6 @@
-use std::{cell::RefCell, cmp::Ordering, collections::{BinaryHeap, HashMap}, fmt, io::{self, BufRead, Write, stdout}, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, error::Error, fmt, io::{self, BufRead, Write}, rc::Rc};
+
+use crate::snippets::graph::dijkstra;
 
 #[derive(Clone, Copy)]
 enum Dir {
@@ -39,6 +41,7 @@ impl fmt::Display for Dir {
 
 #[derive(Debug)]
 struct Block {
+    #[allow(dead_code)] // Only used when debugging the map
     coords: (i8, i8),
     up: Side,
     left: Side,
@@ -124,6 +127,7 @@ impl Default for Block {
 }
 
 #[derive(Debug)]
+#[allow(dead_code)] // The neighbouring blocks are only used when debugging the map
 enum Side {
     Undiscovered,
     Wall,
@@ -139,7 +143,13 @@ impl Default for Side {
 
 const INITIAL_POSITION: (i8, i8) = (0, 0);
 
-pub fn main() {
+/// main entry point of program when invoked directly in the terminal
+#[allow(dead_code)]
+fn main() -> Result<(), Box<dyn Error>> {
+    solve(io::stdin().lock(), io::stdout().lock())
+}
+
+fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
     let mut map: HashMap<(i8, i8), Rc<RefCell<Block>>> = HashMap::new();
 
     // Initial data
@@ -149,12 +159,11 @@ pub fn main() {
 
     let mut cur_path: Vec<Dir> = Vec::new();
 
-    let stdin = io::stdin();
-    let mut lines = stdin.lock().lines();
+    let mut lines = input.lines();
 
     // Print the initial direction
-    println!("{}", last_dir);
-    stdout().flush().expect("Could not write to stdout");
+    writeln!(&mut w, "{}", last_dir)?;
+    w.flush()?;
 
     while let Some(Ok(line)) = lines.next() {
         match &line[..] {
@@ -199,12 +208,12 @@ pub fn main() {
 
         if cur_path.is_empty() {
             // Calculate new path using dijkstra, note that it will return the path in reverse
-            if let Some(path) = dijkstra(cur_pos, &map) {
+            if let Some(path) = path_to_undiscovered(cur_pos, &map) {
                 cur_path = path;
             } else {
                 // There's no path, thus we must conclude that the exit is unreachable.
-                println!("no way out");
-                stdout().flush().expect("Could not write to stdout");
+                writeln!(&mut w, "no way out")?;
+                w.flush()?;
                 continue; // We expect a 'solved' or 'wrong' now
             }
         }
@@ -214,75 +223,40 @@ pub fn main() {
         }
 
         // Print the next direction
-        println!("{}", last_dir);
-        stdout().flush().expect("Could not write to stdout");
-    }
-}
-
-fn dijkstra(from: (i8, i8), map: &HashMap<(i8, i8), Rc<RefCell<Block>>>) -> Option<Vec<Dir>> {
-    // Dijkstra
-    #[derive(Eq, PartialEq)]
-    struct Path {
-        cost: u32,
-        pos: (i8, i8)
+        writeln!(&mut w, "{}", last_dir)?;
+        w.flush()?;
     }
 
-    impl Ord for Path {
-        fn cmp(&self, other: &Self) -> Ordering {
-            other.cost.cmp(&self.cost).then_with(|| other.pos.cmp(&self.pos))
-        }
-    }
+    Ok(())
+}
 
-    impl PartialOrd for Path {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-            Some(self.cmp(other))
+/// Shortest path through the unwalked doors to the nearest undiscovered side, in reverse
+fn path_to_undiscovered(from: (i8, i8), map: &HashMap<(i8, i8), Rc<RefCell<Block>>>) -> Option<Vec<Dir>> {
+    // Every block is a vertex, the extra vertex `undiscovered` is behind every undiscovered side
+    let positions: Vec<(i8, i8)> = map.keys().cloned().collect();
+    let index: HashMap<(i8, i8), usize> = positions.iter().enumerate().map(|(i, &pos)| (pos, i)).collect();
+    let undiscovered = positions.len();
+
+    let mut graph: Vec<Vec<(usize, u32)>> = vec![Vec::new(); positions.len() + 1];
+    for (i, &pos) in positions.iter().enumerate() {
+        for (dir, side) in &map[&pos].borrow().neighbours() {
+            match side {
+                Side::Undiscovered => graph[i].push((undiscovered, 1)),
+                Side::UnwalkedDoor(_) => graph[i].push((index[&dir.apply(pos)], 1)),
+                Side::Wall | Side::WalkedDoor(_) => {}
+            }
         }
     }
 
-    let mut dist: HashMap<(i8, i8), u32> = HashMap::new();
-    dist.insert(from, 0);
-
-    let mut prev: HashMap<(i8, i8), (Dir, (i8, i8))> = HashMap::new();
-
-    let mut heap: BinaryHeap<Path> = BinaryHeap::new();
-    heap.push(Path {
-        cost: 0,
-        pos: from
-    });
+    let path = dijkstra(&graph, &[(index[&from], 0)], Some(undiscovered)).path(undiscovered)?;
+    let (last, blocks) = path[..path.len() - 1].split_last().unwrap();
 
-    while let Some(Path { cost, pos }) = heap.pop() {
-        let v_dist = *dist.entry(pos.clone()).or_insert(std::u32::MAX);
-
-        if cost > v_dist { continue; }
-
-        'inner: for (dir, side) in &map.get(&pos).unwrap().borrow_mut().neighbours() {
-            // Check if found undiscovered
-            if let Side::Undiscovered = side {
-                let mut path = vec![*dir];
-                let mut pos = pos;
-                while let Some((prev_dir, prev_pos)) = prev.get(&pos) {
-                    path.push(*prev_dir);
-                    pos = *prev_pos;
-                }
-                return Some(path);
-            }
-
-            if let Side::Wall | Side::WalkedDoor(_) = side {
-                continue 'inner;
-            }
-
-            let next_pos = dir.apply(pos);
-            if v_dist + 1 < *dist.entry(next_pos.clone()).or_insert(std::u32::MAX) {
-                dist.insert(next_pos.clone(), v_dist + 1);
-                prev.insert(next_pos.clone(), (*dir, pos));
-
-                heap.push(Path {
-                    cost: v_dist + 1,
-                    pos: next_pos
-                });
-            }
-        }
+    // The first undiscovered side of the last block, then the doors between the blocks
+    let last = map[&positions[*last]].borrow();
+    let mut dirs = vec![last.neighbours().iter().find(|(_, side)| matches!(side, Side::Undiscovered))?.0];
+    for (&a, &b) in blocks.iter().zip(&path[1..]).rev() {
+        let (a, b) = (positions[a], positions[b]);
+        dirs.push(*[Dir::Up, Dir::Left, Dir::Down, Dir::Right].iter().find(|dir| dir.apply(a) == b).unwrap());
     }
-
-    None
-}
\ No newline at end of file
+    Some(dirs)
+}
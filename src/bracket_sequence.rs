@@ -1,15 +1,23 @@
-use std::io::{self, BufRead};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
 
 const MOD: u64 = 1000000007;
 
-fn main() {
-    let stdin = io::stdin();
-    for l in stdin.lock().lines().map(|l| l.unwrap()).skip(1) {
+/// main entry point of program when invoked directly in the terminal
+#[allow(dead_code)]
+fn main() -> Result<(), Box<dyn Error>> {
+    solve(io::stdin().lock(), io::stdout().lock())
+}
+
+fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
+    for l in input.lines().map(|l| l.unwrap()).skip(1) {
         let mut seq = l.split(' ');
         let res = add(&mut seq);
-        println!("{:?}", res % MOD);
+        writeln!(&mut w, "{:?}", res % MOD).unwrap();
 
     }
+
+    Ok(())
 }
 
 fn mul<'a>(seq: &mut impl Iterator<Item = &'a str>) -> u64 {
@@ -56,4 +64,12 @@ fn add<'a>(seq: &mut impl Iterator<Item = &'a str>) -> u64 {
     }
     
     total
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn bracket_sequence_sampleinputs() {
+        crate::judge::test_samples("bracket_sequence", |input, output| super::solve(input, output));
+    }
+}
@@ -0,0 +1,653 @@
+//! Bundles a solution and the `snippets` it uses into a single file which can be submitted to Kattis.
+//!
+//! Solutions use the snippets like any other module, e.g. `use crate::snippets::geometry::*;`. The bundle appends a
+//! `mod snippets` to the solution with only the snippet items it needs, so the same paths resolve in the submitted
+//! file. Items are selected with a small tokenizer instead of a full parser: an item is needed when its name is used,
+//! impl blocks are kept for needed types and inherent methods only when their name is used somewhere.
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::ops::Range;
+
+use crate::problem::root;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Ident,
+    Lifetime,
+    Literal,
+    Punct,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    kind: Kind,
+    start: usize,
+    end: usize,
+}
+
+/// Splits Rust source into tokens, comments and whitespace are skipped
+fn tokenize(text: &str) -> Vec<Token> {
+    let b = text.as_bytes();
+    let at = |i: usize| b.get(i).copied().unwrap_or(0);
+    let ident = |c: u8| c.is_ascii_alphanumeric() || c == b'_';
+
+    // End of a (raw) string starting at `i`, which points to the opening quote or the `r`
+    let string_end = |mut i: usize| -> usize {
+        if at(i) == b'r' {
+            let hashes = b[i + 1..].iter().take_while(|&&c| c == b'#').count();
+            let close = format!("\"{}", "#".repeat(hashes));
+            i += hashes + 2;
+            return text[i..].find(&close).map_or(b.len(), |n| i + n + close.len());
+        }
+        i += 1;
+        while i < b.len() && b[i] != b'"' {
+            i += if b[i] == b'\\' { 2 } else { 1 };
+        }
+        (i + 1).min(b.len())
+    };
+
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < b.len() {
+        let start = i;
+        let kind = match b[i] {
+            c if c.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'/' if at(i + 1) == b'/' => {
+                i = text[i..].find('\n').map_or(b.len(), |n| i + n);
+                continue;
+            }
+            b'/' if at(i + 1) == b'*' => {
+                let mut depth = 0;
+                while i < b.len() {
+                    if b[i..].starts_with(b"/*") {
+                        depth += 1;
+                        i += 2;
+                    } else if b[i..].starts_with(b"*/") {
+                        depth -= 1;
+                        i += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    } else {
+                        i += 1;
+                    }
+                }
+                continue;
+            }
+            b'"' => {
+                i = string_end(i);
+                Kind::Literal
+            }
+            b'b' if at(i + 1) == b'"' || (at(i + 1) == b'r' && matches!(at(i + 2), b'"' | b'#')) => {
+                i = string_end(i + 1);
+                Kind::Literal
+            }
+            b'r' if at(i + 1) == b'"' || (at(i + 1) == b'#' && matches!(at(i + 2), b'"' | b'#')) => {
+                i = string_end(i);
+                Kind::Literal
+            }
+            b'\'' => {
+                // A character literal when a quote follows the (escaped) character, a lifetime otherwise
+                let len = text[i + 1..].chars().next().map_or(1, char::len_utf8);
+                if at(i + 1) == b'\\' {
+                    i += 3;
+                    while i < b.len() && b[i] != b'\'' {
+                        i += 1;
+                    }
+                    i += 1;
+                    Kind::Literal
+                } else if at(i + 1 + len) == b'\'' {
+                    i += len + 2;
+                    Kind::Literal
+                } else {
+                    i += 1;
+                    while ident(at(i)) {
+                        i += 1;
+                    }
+                    Kind::Lifetime
+                }
+            }
+            c if c.is_ascii_digit() => {
+                while ident(at(i)) || (at(i) == b'.' && at(i + 1).is_ascii_digit()) {
+                    i += 1;
+                }
+                Kind::Literal
+            }
+            c if ident(c) => {
+                while ident(at(i)) {
+                    i += 1;
+                }
+                Kind::Ident
+            }
+            _ => {
+                i += if b[i..].starts_with(b"::") || b[i..].starts_with(b"->") || b[i..].starts_with(b"=>") {
+                    2
+                } else {
+                    text[i..].chars().next().unwrap().len_utf8()
+                };
+                Kind::Punct
+            }
+        };
+        tokens.push(Token { kind, start, end: i.min(b.len()) });
+    }
+
+    tokens
+}
+
+/// A top level item or a member of an impl block. The span starts at the end of the previous item, so it includes
+/// the doc comments and attributes of the item and concatenating all spans gives back the source.
+#[derive(Debug, Clone)]
+struct Item {
+    span: Range<usize>,
+    tokens: Range<usize>,
+    def: Def,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Def {
+    /// A struct, enum, trait, function, type alias, constant or module with its name
+    Named(String),
+    /// An impl block of the type with the index of its opening brace. `traits` names the implemented trait.
+    Impl { ty: String, traits: Option<String>, open: usize },
+    Use,
+    /// Anything else, like a macro invocation, is always kept
+    Other,
+    /// Items only compiled in tests, which are never bundled
+    Test,
+}
+
+struct Source<'a> {
+    text: &'a str,
+    tokens: Vec<Token>,
+}
+
+impl<'a> Source<'a> {
+    fn new(text: &'a str) -> Self {
+        Source { text, tokens: tokenize(text) }
+    }
+
+    fn str(&self, i: usize) -> &'a str {
+        self.tokens.get(i).map_or("", |t| &self.text[t.start..t.end])
+    }
+
+    fn is_punct(&self, i: usize, punct: &str) -> bool {
+        self.tokens.get(i).is_some_and(|t| t.kind == Kind::Punct) && self.str(i) == punct
+    }
+
+    fn is_ident(&self, i: usize) -> bool {
+        self.tokens.get(i).is_some_and(|t| t.kind == Kind::Ident)
+    }
+
+    /// Index after the group opened at `i`, or after `i` itself when it does not open a group
+    fn skip_group(&self, mut i: usize) -> usize {
+        let mut depth = 0;
+        while i < self.tokens.len() {
+            match self.str(i) {
+                "(" | "[" | "{" if self.is_punct(i, self.str(i)) => depth += 1,
+                ")" | "]" | "}" if self.is_punct(i, self.str(i)) => depth -= 1,
+                _ => {}
+            }
+            i += 1;
+            if depth <= 0 {
+                break;
+            }
+        }
+        i
+    }
+
+    /// Splits the tokens into items, `offset` is the start of the text before the first token
+    fn items(&self, tokens: Range<usize>, offset: usize) -> Vec<Item> {
+        let mut items = Vec::new();
+        let (mut begin, mut offset, mut depth) = (tokens.start, offset, 0);
+
+        for i in tokens {
+            let punct = self.tokens[i].kind == Kind::Punct;
+            match self.str(i) {
+                "(" | "[" | "{" if punct => depth += 1,
+                ")" | "]" | "}" if punct => depth -= 1,
+                _ => {}
+            }
+
+            let def = || self.def(begin..i + 1);
+            let end = depth == 0
+                && punct
+                && match self.str(i) {
+                    ";" => true,
+                    // Constants and aliases can contain braces, `const X: S = S { a: 1 };`, but only end at the semicolon
+                    "}" => !matches!(self.keyword(begin..i + 1).map(|k| self.str(k)), Some("const" | "static" | "type" | "use" | "let")),
+                    _ => false,
+                };
+
+            if end {
+                items.push(Item { span: offset..self.tokens[i].end, tokens: begin..i + 1, def: def() });
+                offset = self.tokens[i].end;
+                begin = i + 1;
+            }
+        }
+
+        items
+    }
+
+    /// Index of the keyword of the item, after its attributes and visibility
+    fn keyword(&self, tokens: Range<usize>) -> Option<usize> {
+        let mut i = tokens.start;
+        while i < tokens.end {
+            match self.str(i) {
+                "#" => i = self.skip_group(if self.str(i + 1) == "!" { i + 2 } else { i + 1 }),
+                "pub" if self.str(i + 1) == "(" => i = self.skip_group(i + 1),
+                "pub" | "unsafe" | "async" | "extern" | "default" => i += 1,
+                "const" if matches!(self.str(i + 1), "fn" | "unsafe") => i += 1,
+                _ if self.tokens[i].kind == Kind::Literal => i += 1,
+                _ => return Some(i),
+            }
+        }
+        None
+    }
+
+    fn is_test(&self, tokens: Range<usize>) -> bool {
+        let end = self.keyword(tokens.clone()).unwrap_or(tokens.end);
+        (tokens.start..end).any(|i| self.str(i) == "cfg" && self.str(i + 1) == "(" && self.str(i + 2) == "test" && self.str(i + 3) == ")")
+    }
+
+    fn def(&self, tokens: Range<usize>) -> Def {
+        if self.is_test(tokens.clone()) {
+            return Def::Test;
+        }
+
+        let k = match self.keyword(tokens.clone()) {
+            Some(k) => k,
+            None => return Def::Other,
+        };
+
+        match self.str(k) {
+            "struct" | "enum" | "union" | "trait" | "type" | "fn" | "const" | "static" | "mod" if self.is_ident(k + 1) => {
+                Def::Named(self.str(k + 1).to_string())
+            }
+            "use" => Def::Use,
+            "impl" => {
+                // Skip the generics of the impl itself
+                let mut i = k + 1;
+                if self.str(i) == "<" {
+                    let mut angle = 0;
+                    while i < tokens.end {
+                        match self.str(i) {
+                            "<" => angle += 1,
+                            ">" => angle -= 1,
+                            _ => {}
+                        }
+                        i += 1;
+                        if angle == 0 {
+                            break;
+                        }
+                    }
+                }
+
+                let header = i;
+                let mut open = header;
+                while open < tokens.end && !self.is_punct(open, "{") {
+                    open = if self.is_punct(open, "(") || self.is_punct(open, "[") { self.skip_group(open) } else { open + 1 };
+                }
+
+                let name_after = |mut i: usize| {
+                    while i < open && !self.is_ident(i) || matches!(self.str(i), "mut" | "dyn") {
+                        i += 1;
+                    }
+                    self.str(i).to_string()
+                };
+
+                match (header..open).find(|&i| self.str(i) == "for") {
+                    Some(f) => Def::Impl { ty: name_after(f + 1), traits: Some(name_after(header)), open },
+                    None => Def::Impl { ty: name_after(header), traits: None, open },
+                }
+            }
+            _ => Def::Other,
+        }
+    }
+
+    /// All identifiers in the tokens and the snippet modules referred to by `snippets::<module>` or `super::<module>`
+    fn idents(&self, tokens: Range<usize>, used: &mut HashSet<String>, modules: &mut Vec<String>) {
+        for i in tokens {
+            if self.is_ident(i) {
+                used.insert(self.str(i).to_string());
+                if matches!(self.str(i), "snippets" | "super") && self.str(i + 1) == "::" && self.is_ident(i + 2) {
+                    let module = self.str(i + 2).to_string();
+                    if !modules.contains(&module) {
+                        modules.push(module);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A snippet module with the items selected so far, for impl blocks the selected members are tracked separately
+struct Module {
+    name: String,
+    text: String,
+    items: Vec<Item>,
+    kept: Vec<bool>,
+    members: Vec<Vec<(Item, bool)>>,
+}
+
+impl Module {
+    fn load(name: &str) -> Result<Self, Box<dyn Error>> {
+        let dir = root().join("src").join("snippets");
+        let path = vec![dir.join(format!("{}.rs", name)), dir.join(name).join("mod.rs")].into_iter().find(|p| p.is_file());
+        let text = match path {
+            Some(path) => fs::read_to_string(path)?,
+            None => return Err(format!("unknown snippet module '{}', it should be in {}", name, dir.display()).into()),
+        };
+
+        let source = Source::new(&text);
+        let items = source.items(0..source.tokens.len(), 0);
+        let members = items
+            .iter()
+            .map(|item| match item.def {
+                Def::Impl { open, .. } => {
+                    let members = source.items(open + 1..item.tokens.end - 1, source.tokens[open].end);
+                    members.into_iter().map(|m| (m, false)).collect()
+                }
+                _ => Vec::new(),
+            })
+            .collect();
+
+        Ok(Module { name: name.to_string(), kept: vec![false; items.len()], items, members, text })
+    }
+
+    /// Selects the items needed by the used identifiers, returns whether anything was added
+    fn select(&mut self, types: &HashSet<String>, used: &mut HashSet<String>, modules: &mut Vec<String>) -> bool {
+        let source = Source::new(&self.text);
+        let mut changed = false;
+
+        for (j, item) in self.items.iter().enumerate() {
+            let needed = match &item.def {
+                Def::Named(name) => used.contains(name),
+                Def::Other => true,
+                Def::Impl { ty, traits, .. } => types.contains(ty) || traits.as_ref().is_some_and(|t| types.contains(t)),
+                Def::Use | Def::Test => false,
+            };
+            if !needed {
+                continue;
+            }
+
+            if let Def::Impl { traits, open, .. } = &item.def {
+                // Trait impls need all their members, inherent impls only the ones which are used
+                for (member, kept) in self.members[j].iter_mut() {
+                    let needed = match &member.def {
+                        Def::Named(name) => traits.is_some() || used.contains(name),
+                        Def::Test => false,
+                        _ => true,
+                    };
+                    if needed && !*kept {
+                        *kept = true;
+                        changed = true;
+                        source.idents(member.tokens.clone(), used, modules);
+                    }
+                }
+
+                // Marker trait impls like `impl Error for E {}` have no members at all
+                if !self.kept[j] && (traits.is_some() || self.members[j].iter().any(|(_, kept)| *kept)) {
+                    self.kept[j] = true;
+                    changed = true;
+                    source.idents(item.tokens.start..*open, used, modules);
+                }
+            } else if !self.kept[j] {
+                self.kept[j] = true;
+                changed = true;
+                source.idents(item.tokens.clone(), used, modules);
+            }
+        }
+
+        changed
+    }
+
+    /// Names of the selected types and traits, impl blocks for them are needed as well
+    fn types(&self) -> impl Iterator<Item = &String> + '_ {
+        self.items.iter().zip(&self.kept).filter_map(|(item, &kept)| match &item.def {
+            Def::Named(name) if kept => Some(name),
+            _ => None,
+        })
+    }
+
+    /// The source of the selected items, indented to be placed in `mod snippets`
+    fn bundle(&self, used: &HashSet<String>) -> String {
+        let source = Source::new(&self.text);
+        let mut out = String::new();
+
+        for (j, item) in self.items.iter().enumerate() {
+            match &item.def {
+                Def::Use => {
+                    if let Some(text) = prune_use(&source, item, used) {
+                        out.push_str(&text);
+                    }
+                }
+                Def::Impl { open, .. } if self.kept[j] => {
+                    let members = &self.members[j];
+                    let tail = members.last().map_or(source.tokens[*open].end, |(m, _)| m.span.end);
+                    indent(&source, item.span.start..source.tokens[*open].end, &mut out);
+                    for (i, (member, _)) in members.iter().filter(|(_, kept)| *kept).enumerate() {
+                        // The first member only keeps a single line break after the brace if earlier ones were dropped
+                        let mut span = member.span.clone();
+                        if i == 0 {
+                            let text = &self.text[span.clone()];
+                            span.start += text[..text.len() - text.trim_start().len()].rfind('\n').unwrap_or(0);
+                        }
+                        indent(&source, span, &mut out);
+                    }
+                    indent(&source, tail..item.span.end, &mut out);
+                }
+                _ if self.kept[j] => indent(&source, item.span.clone(), &mut out),
+                _ => {}
+            }
+        }
+
+        out
+    }
+}
+
+/// The use declaration with only the imported names which are used, `None` if none of them is
+fn prune_use(source: &Source, item: &Item, used: &HashSet<String>) -> Option<String> {
+    let open = item.tokens.clone().find(|&i| source.is_punct(i, "{"));
+    let names = |range: Range<usize>| -> Option<Vec<(String, String)>> {
+        // Only plain `name` and `name as alias` entries are pruned
+        let mut names = Vec::new();
+        let tokens: Vec<usize> = range.collect();
+        for entry in tokens.split(|&i| source.is_punct(i, ",")).filter(|e| !e.is_empty()) {
+            match entry {
+                [name] if source.is_ident(*name) => names.push((source.str(*name).to_string(), source.str(*name).to_string())),
+                [name, r#as, alias] if source.str(*r#as) == "as" => {
+                    names.push((format!("{} as {}", source.str(*name), source.str(*alias)), source.str(*alias).to_string()))
+                }
+                _ => return None,
+            }
+        }
+        Some(names)
+    };
+
+    let entries = match open {
+        Some(open) => names(open + 1..item.tokens.end - 2),
+        None => {
+            let last = item.tokens.end - 2;
+            let start = if source.str(last - 1) == "as" { last - 2 } else { last };
+            names(start..last + 1).filter(|_| source.str(start - 1) == "::")
+        }
+    };
+
+    let text = &source.text[item.span.clone()];
+    let entries = match entries {
+        Some(entries) => entries,
+        None => return Some(text.to_string()),
+    };
+
+    // `self` imports the module itself, which has the name of the last path segment
+    let kept: Vec<&str> = entries
+        .iter()
+        .filter(|(entry, name)| used.contains(name) || (entry == "self" && open.is_some_and(|o| used.contains(source.str(o - 2)))))
+        .map(|(entry, _)| entry.as_str())
+        .collect();
+
+    match (kept.len(), open) {
+        (0, _) => None,
+        (n, Some(open)) if n < entries.len() => {
+            let mut out = String::new();
+            indent(source, item.span.start..source.tokens[open].end, &mut out);
+            out.push_str(&kept.join(", "));
+            out.push_str("};");
+            Some(out)
+        }
+        _ => {
+            let mut out = String::new();
+            indent(source, item.span.clone(), &mut out);
+            Some(out)
+        }
+    }
+}
+
+/// Appends the text indented by two levels, lines starting inside a string literal are left alone
+fn indent(source: &Source, span: Range<usize>, out: &mut String) {
+    let in_string = |offset: usize| source.tokens.iter().any(|t| t.kind == Kind::Literal && t.start < offset && offset < t.end);
+
+    let mut start = span.start;
+    for line in source.text[span].split_inclusive('\n') {
+        if (start == 0 || source.text.as_bytes()[start - 1] == b'\n') && !line.trim().is_empty() && !in_string(start) {
+            out.push_str("        ");
+        }
+        out.push_str(line);
+        start += line.len();
+    }
+}
+
+/// The identifiers and punctuation of Rust source with their byte offset, comments, literals and lifetimes are skipped
+pub(crate) fn code_tokens(text: &str) -> impl Iterator<Item = (usize, &str)> + '_ {
+    tokenize(text)
+        .into_iter()
+        .filter(|t| t.kind == Kind::Ident || t.kind == Kind::Punct)
+        .map(move |t| (t.start, &text[t.start..t.end]))
+}
+
+/// Bundles the source of a solution into a single self-contained file. The solution keeps its line numbers, so
+/// compiler errors point to the same lines as in `src/<problem>.rs`.
+pub(crate) fn bundle(solution: &str) -> Result<String, Box<dyn Error>> {
+    let source = Source::new(solution);
+    let items = source.items(0..source.tokens.len(), 0);
+
+    let mut used = HashSet::new();
+    let mut names = Vec::new();
+    let mut out = String::new();
+    let mut end = 0;
+
+    for item in &items {
+        if item.def == Def::Test {
+            // Tests are replaced by empty lines to keep the line numbers of everything below them
+            out.push_str(&"\n".repeat(solution[item.span.clone()].matches('\n').count()));
+        } else {
+            source.idents(item.tokens.clone(), &mut used, &mut names);
+            out.push_str(&solution[item.span.clone()]);
+        }
+        end = item.span.end;
+    }
+    out.push_str(&solution[end..]);
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+
+    let mut modules: Vec<Module> = Vec::new();
+    loop {
+        while let Some(name) = names.iter().find(|n| !modules.iter().any(|m| &m.name == *n)) {
+            modules.push(Module::load(name)?);
+        }
+
+        let types: HashSet<String> = modules.iter().flat_map(Module::types).cloned().collect();
+        let mut changed = false;
+        for module in modules.iter_mut() {
+            changed |= module.select(&types, &mut used, &mut names);
+        }
+
+        if !changed && names.len() == modules.len() {
+            break;
+        }
+    }
+
+    if !modules.is_empty() {
+        out.push_str("\n// Snippets used by this solution, inlined from src/snippets by `kattis bundle`\n");
+        out.push_str("#[allow(dead_code)]\nmod snippets {\n");
+        for (i, module) in modules.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            out.push_str(&format!("    pub mod {} {{\n", module.name));
+            out.push_str(module.bundle(&used).trim_matches('\n'));
+            out.push_str("\n    }\n");
+        }
+        out.push_str("}\n");
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problem::Problem;
+
+    #[test]
+    fn strip_tests() {
+        let solution = "fn main() {}\n\n#[cfg(test)]\nmod tests {\n    fn t() { let s = \"}\"; }\n}\n";
+        assert_eq!(bundle(solution).unwrap(), "fn main() {}\n");
+
+        let solution = "#[cfg(test)]\nuse x;\nconst C: S = S { a: '{' };\nfn main() {}\n";
+        assert_eq!(bundle(solution).unwrap(), "\n\nconst C: S = S { a: '{' };\nfn main() {}\n");
+    }
+
+    /// Every solution using snippets, with parts of the snippets its bundle needs and parts it should leave out.
+    /// Interactive solutions like amazing are left out, their own test compiles them.
+    const BUNDLES: [(&str, &[&str], &[&str]); 15] = [
+        (
+            "cleaningpipes",
+            &["pub struct LineSegment<T>", "pub fn proper_intersect(", "pub fn orient("],
+            &["#[cfg(test)]", "pub struct Circle<", "pub fn distance_to_segment(", "pub fn angle("],
+        ),
+        ("mosquitoes", &["pub struct Point<T>", "pub fn in_circle("], &["#[cfg(test)]", "LineSegment", "fn cross(", "fn angle("]),
+        (
+            "rafting",
+            &["pub fn distance_to_segment(", "pub fn distance_to_point(", "pub fn cmp_proj("],
+            &["#[cfg(test)]", "pub struct Circle<", "pub fn from_equation(", "pub fn angle("],
+        ),
+        ("onewayroads", &["pub struct Scanner<R>"], &[]),
+        (
+            "water",
+            &["pub struct Scanner<R>", "impl Error for ScanError {}", "Tuple for (A, B, C)", "pub fn raise_capacity("],
+            &["pub fn at_end(", "pub fn edges_from("],
+        ),
+        ("closestpair2", &["pub fn rescale(", "impl Eq for Fixed {}"], &["pub fn to_f64("]),
+        ("detour", &["pub fn dijkstra<C>(", "pub fn path("], &[]),
+        ("visualgo", &["pub fn dijkstra<C>("], &[]),
+        ("bumped", &["pub fn dijkstra<C>("], &[]),
+        ("primepath", &["pub fn dijkstra<C>("], &[]),
+        ("firestation", &["pub fn dijkstra<C>("], &[]),
+        ("emptyingbaltic", &["pub fn dijkstra<C>("], &["pub fn path("]),
+        ("ticketpricing", &["pub fn dijkstra<C>("], &["pub fn path("]),
+        ("paintball", &["pub struct FlowGraph<C>"], &[]),
+        ("pianolessons", &["pub struct FlowGraph<C>"], &[]),
+    ];
+
+    #[test]
+    fn bundle_snippets() {
+        // The bundle is what gets compiled, so every solution using snippets has to compile on its own. A single test
+        // compiles each problem once, tests running in parallel would write the same binary.
+        for (name, kept, dropped) in BUNDLES.iter() {
+            let problem = Problem::new(name).unwrap();
+            let bundled = bundle(&fs::read_to_string(problem.source()).unwrap()).unwrap();
+            for part in kept.iter() {
+                assert!(bundled.contains(part), "the bundle of {} lacks {}", name, part);
+            }
+            for part in dropped.iter() {
+                assert!(!bundled.contains(part), "the bundle of {} contains {}", name, part);
+            }
+            problem.compile().unwrap();
+        }
+    }
+}
@@ -1,12 +1,17 @@
 use std::{
     error::Error,
-    io::{self, BufRead},
+    io::{self, BufRead, Write},
 };
 
-// Kattis problem: chineseremainder
+/// main entry point of program when invoked directly in the terminal
+#[allow(dead_code)]
 fn main() -> Result<(), Box<dyn Error>> {
-    let stdin = io::stdin();
-    for l in stdin.lock().lines().map(|l| l.unwrap()).skip(1) {
+    solve(io::stdin().lock(), io::stdout().lock())
+}
+
+// Kattis problem: chineseremainder
+fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
+    for l in input.lines().map(|l| l.unwrap()).skip(1) {
         let mut seq = l.split(' ');
         let (x, k) = test_case(
             seq.next().unwrap().parse()?,
@@ -14,7 +19,7 @@ fn main() -> Result<(), Box<dyn Error>> {
             seq.next().unwrap().parse()?,
             seq.next().unwrap().parse()?,
         );
-        println!("{} {}", x, k)
+        writeln!(&mut w, "{} {}", x, k)?;
     }
 
     Ok(())
@@ -58,3 +63,11 @@ fn modulus_inverse(m_i: i128, modulus: i128) -> Option<i128> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn chineseremainder_sampleinputs() {
+        crate::judge::test_samples("chineseremainder", |input, output| super::solve(input, output));
+    }
+}
@@ -1,138 +1,14 @@
 use std::error::Error;
 use std::io::{self, BufRead, Write};
 
+use crate::snippets::geometry::*;
+
 /// main entry point of program when invoked directly in the terminal
 #[allow(dead_code)]
 fn main() -> Result<(), Box<dyn Error>> {
     solve(io::stdin().lock(), io::stdout().lock())
 }
 
-/// This file is based on the Handbook of geometry for competitive programmers by Victor Lecomte
-
-use std::ops::{Div, Mul, Sub, Add, Neg};
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Clone)]
-pub struct Point<T> {
-    pub x: T,
-    pub y: T,
-}
-
-impl<T: Add<Output=T> + Copy> Add for &Point<T> {
-    type Output = Point<T>;
-
-    fn add(self, other: Self) -> Point<T> {
-        Point {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
-    }
-}
-
-impl<T: Sub<Output=T> + Copy> Sub for &Point<T> {
-    type Output = Point<T>;
-
-    fn sub(self, other: Self) -> Point<T> {
-        Point {
-            x: self.x - other.x,
-            y: self.y - other.y,
-        }
-    }
-}
-
-impl<T: Mul<Output=T> + Copy> Mul<T> for &Point<T> {
-    type Output = Point<T>;
-
-    fn mul(self, rhs: T) -> Point<T> {
-        Point {
-            x: self.x * rhs,
-            y: self.y * rhs
-        }
-    }
-}
-
-impl<T: Div<Output=T> + Copy> Div<T> for &Point<T> {
-    type Output = Point<T>;
-
-    fn div(self, rhs: T) -> Point<T> {
-        Point {
-            x: self.x / rhs,
-            y: self.y / rhs
-        }
-    }
-}
-
-impl<T: Neg<Output=T> + Copy> Neg for &Point<T> {
-    type Output = Point<T>;
-
-    fn neg(self) -> Point<T> {
-        Point {
-            x: -self.x,
-            y: -self.y
-        }
-    }
-}
-
-impl<T: Add<Output=T> + Mul<Output=T> + Sub<Output=T> + Copy> Point<T> {
-    pub fn square(&self) -> T {
-        return self.x * self.x + self.y * self.y;
-    }
-
-    pub fn dot(&self, other: &Self) -> T {
-        self.x * other.x + self.y * other.y
-    }
-
-    pub fn cross(&self, other: &Self) -> T {
-        self.x * other.y - self.y * other.x
-    }
-}
-
-impl Point<f64> {
-    pub fn abs(&self) -> f64 {
-        self.square().sqrt()
-    }
-
-    pub fn is_perp(&self, other: &Self) -> bool {
-        self.dot(other) == 0.0
-    }
-
-    pub fn angle(&self, other: &Self) -> f64 {
-        let cos_theta = self.dot(other) / self.abs() / other.abs();
-        f64::max(-1.0, f64::min(1.0, cos_theta)).acos()
-    }
-
-    pub fn orient(a: &Point<f64>, b: &Point<f64>, c: &Point<f64>) -> f64 {
-        (b - a).cross(&(c - a))
-    }
-}
-
-#[derive(Debug, PartialEq, Eq)]
-pub struct LineSegment<'a, 'b, T>(pub &'a Point<T>, pub &'b Point<T>);
-
-impl LineSegment<'_, '_, f64> {
-    pub fn proper_intersect(&self, other: &LineSegment<f64>) -> Option<Point<f64>> {
-        if self.0 == other.0 {
-            Some(self.0.clone())
-        } else if self.0 == other.1 {
-            Some(self.0.clone())
-        } else if self.1 == other.0 {
-            Some(self.1.clone())
-        } else if self.1 == other.1 {
-            Some(self.1.clone())
-        } else {
-            let oa = Point::orient(other.0, other.1, self.0);
-            let ob = Point::orient(other.0, other.1, self.1);
-            let oc = Point::orient(self.0, self.1, other.0);
-            let od = Point::orient(self.0, self.1, other.1);
-
-            if oa * ob < 0.0 && oc * od < 0.0 {
-                Some(&(&(self.0 * ob) - &(self.1 * oa)) / (ob - oa))
-            } else {
-                None
-            }
-        }
-    }
-}
-
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum Color {
     Red,
@@ -180,10 +56,10 @@ fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
     for (i1, (w1, p1)) in pipes.iter().enumerate() {
         for (i2, (w2, p2)) in pipes.iter().enumerate() {
             if i2 > i1 && w1 != w2 { // Pipes from the same well only intersect at the well itself.
-                let s1 = LineSegment(&wells[w1 - 1], p1);
-                let s2 = LineSegment(&wells[w2 - 1], p2);
+                let s1 = LineSegment(wells[w1 - 1].clone(), p1.clone());
+                let s2 = LineSegment(wells[w2 - 1].clone(), p2.clone());
 
-                if let Some(_) = s1.proper_intersect(&s2) {
+                if intersect(&s1, &s2) {
                     edges[i1].push(i2);
                     edges[i2].push(i1);
                 }
@@ -213,6 +89,11 @@ fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Pipes also intersect when they share an end point, which is not a proper intersection
+fn intersect(s1: &LineSegment<f64>, s2: &LineSegment<f64>) -> bool {
+    s1.0 == s2.0 || s1.0 == s2.1 || s1.1 == s2.0 || s1.1 == s2.1 || s1.proper_intersect(s2).is_some()
+}
+
 fn dfs(node: usize, edges: &Vec<Vec<usize>>, visited: &mut Vec<bool>, color: &mut Vec<Color>) -> bool {
     let mut to_visit = vec![node];
     let mut found_both = false;
@@ -240,28 +121,8 @@ fn dfs(node: usize, edges: &Vec<Vec<usize>>, visited: &mut Vec<bool>, color: &mu
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn cleaningpipes_sampleinputs() {
-        for mut file in std::fs::read_dir("input/cleaningpipes")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert_eq!(
-                std::str::from_utf8(&output_writer).unwrap().trim(),
-                std::fs::read_to_string(&file).unwrap().trim(),
-                "file: {:?}",
-                file
-            );
-        }
+        crate::judge::test_samples("cleaningpipes", |input, output| super::solve(input, output));
     }
 }
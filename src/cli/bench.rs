@@ -0,0 +1,184 @@
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::judge::execute;
+use crate::problem::Problem;
+
+/// A benchmark of a single input, times are CPU times in seconds
+#[derive(Debug, Clone, PartialEq)]
+struct Record {
+    timestamp: u64,
+    input: String,
+    runs: usize,
+    median: f64,
+    min: f64,
+}
+
+impl Record {
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        Some(Record {
+            timestamp: fields.next()?.parse().ok()?,
+            input: fields.next()?.to_string(),
+            runs: fields.next()?.parse().ok()?,
+            median: fields.next()?.parse().ok()?,
+            min: fields.next()?.parse().ok()?,
+        })
+    }
+
+    fn line(&self) -> String {
+        format!("{}\t{}\t{}\t{:.6}\t{:.6}", self.timestamp, self.input, self.runs, self.median, self.min)
+    }
+}
+
+/// Survives `cargo clean` but is ignored by git, the timings only compare with runs on the same machine
+fn history_path(problem: &Problem) -> PathBuf {
+    problem.input_dir().join("bench.tsv")
+}
+
+fn history(path: &Path) -> Result<Vec<Record>, Box<dyn Error>> {
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    Ok(fs::read_to_string(path)?.lines().filter(|l| !l.starts_with('#')).filter_map(Record::parse).collect())
+}
+
+/// The large inputs written by `kattis gen`, without them the samples are used
+fn inputs(problem: &Problem) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let generated = problem.generated()?;
+    if !generated.is_empty() {
+        return Ok(generated.into_iter().map(|s| s.input).collect());
+    }
+
+    println!("no inputs generated with `kattis gen {}`, using the samples instead", problem.name());
+    Ok(problem.samples()?.into_iter().map(|s| s.input).collect())
+}
+
+/// Relative change of the median compared to the best median recorded before for the same input, `None` without
+/// history
+fn change(history: &[Record], record: &Record) -> Option<f64> {
+    let best = history.iter().filter(|r| r.input == record.input).map(|r| r.median).fold(f64::INFINITY, f64::min);
+    if best.is_finite() && best > 0.0 {
+        Some(record.median / best - 1.0)
+    } else {
+        None
+    }
+}
+
+fn median(times: &mut [Duration]) -> Duration {
+    times.sort();
+    let n = times.len();
+    if n % 2 == 1 {
+        times[n / 2]
+    } else {
+        (times[n / 2 - 1] + times[n / 2]) / 2
+    }
+}
+
+/// Runs the release build on the large inputs of the problem, the results are appended to the history and compared
+/// with the best median so far. A median more than `threshold` percent slower than the best is a regression.
+pub(super) fn bench(problem: &Problem, runs: usize, threshold: f64) -> Result<(), Box<dyn Error>> {
+    if runs == 0 {
+        return Err("at least one run is needed".into());
+    }
+
+    let inputs = inputs(problem)?;
+    if inputs.is_empty() {
+        return Err(format!("no inputs found for {}", problem.name()).into());
+    }
+
+    let binary = problem.compile()?;
+    let limits = problem.metadata()?.limits;
+    let path = history_path(problem);
+    let history = history(&path)?;
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    println!("{:<24} {:>5} {:>9} {:>9} {:>9}", "input", "runs", "median", "min", "change");
+
+    let mut records = Vec::new();
+    let mut regressions = Vec::new();
+    for input in &inputs {
+        let name = input.file_name().unwrap().to_string_lossy().into_owned();
+
+        let mut times = Vec::new();
+        for _ in 0..runs {
+            let mut command = Command::new(&binary);
+            command.stdin(File::open(input)?).stdout(Stdio::null()).stderr(Stdio::null());
+            let execution = execute(command, &limits)?;
+            if let Some(verdict) = execution.verdict(&limits, "") {
+                return Err(format!("{} failed on {}: {}", problem.name(), name, verdict).into());
+            }
+            times.push(execution.cpu);
+        }
+
+        let record = Record {
+            timestamp,
+            input: name,
+            runs,
+            median: median(&mut times).as_secs_f64(),
+            min: times[0].as_secs_f64(),
+        };
+        let change = change(&history, &record);
+
+        println!(
+            "{:<24} {:>5} {:>8.3}s {:>8.3}s {:>9}",
+            record.input,
+            record.runs,
+            record.median,
+            record.min,
+            change.map_or("-".to_string(), |c| format!("{:+.1}%", c * 100.0))
+        );
+
+        if change.is_some_and(|c| c * 100.0 > threshold) {
+            regressions.push(record.input.clone());
+        }
+        records.push(record);
+    }
+
+    fs::create_dir_all(path.parent().unwrap())?;
+    let new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    if new {
+        writeln!(file, "# timestamp\tinput\truns\tmedian\tmin")?;
+    }
+    for record in &records {
+        writeln!(file, "{}", record.line())?;
+    }
+
+    if !regressions.is_empty() {
+        return Err(format!(
+            "{} is more than {}% slower than its best median on {}",
+            problem.name(),
+            threshold,
+            regressions.join(", ")
+        )
+        .into());
+    }
+
+    println!("history saved to {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regressions() {
+        let record = |input: &str, median: f64| Record { timestamp: 0, input: input.to_string(), runs: 5, median, min: median };
+        let history = vec![record("1.in", 0.5), record("1.in", 0.4), record("2.in", 1.0)];
+
+        assert_eq!(Record::parse(&history[1].line()), Some(history[1].clone()));
+        assert!((change(&history, &record("1.in", 0.5)).unwrap() - 0.25).abs() < 1e-9);
+        assert!((change(&history, &record("2.in", 0.9)).unwrap() + 0.1).abs() < 1e-9);
+        assert_eq!(change(&history, &record("3.in", 0.9)), None);
+
+        let mut times = [3, 1, 2, 4].map(Duration::from_secs);
+        assert_eq!(median(&mut times), Duration::from_millis(2500));
+        assert_eq!(times[0], Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,17 @@
+use std::error::Error;
+use std::fs;
+
+use crate::problem::Problem;
+
+/// Prints the bundled solution, or writes it to the given file, ready to be submitted to Kattis
+pub(super) fn bundle(problem: &Problem, output: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let bundled = problem.bundle()?;
+
+    match output {
+        Some(path) => fs::write(path, bundled).map_err(|e| format!("could not write {}: {}", path, e).into()),
+        None => {
+            print!("{}", bundled);
+            Ok(())
+        }
+    }
+}
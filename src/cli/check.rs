@@ -0,0 +1,142 @@
+use std::error::Error;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::judge::{execute, Checker, Limits};
+use crate::problem::{root, Problem};
+
+/// The location and message of a panic in the stderr of a solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Panic {
+    location: String,
+    message: String,
+}
+
+fn panic(stderr: &str) -> Option<Panic> {
+    let start = stderr.find("panicked at ")? + "panicked at ".len();
+    let mut lines = stderr[start..].lines();
+    let location = lines.next()?.trim_end_matches(':').to_string();
+    Some(Panic { location, message: lines.next().unwrap_or("").to_string() })
+}
+
+/// Maps a location in the bundled file to the solution, the bundle keeps the line numbers of the solution and
+/// appends the snippets after its last line
+fn locate(location: &str, bundled: &Path, source: &Path, solution_lines: usize) -> String {
+    let bundled = bundled.display().to_string();
+    let position = match location.strip_prefix(&bundled) {
+        Some(position) => position,
+        None => return location.to_string(),
+    };
+    let line = position.split(':').nth(1).and_then(|l| l.parse::<usize>().ok());
+
+    match line {
+        Some(line) if line <= solution_lines => {
+            format!("{}{}", source.strip_prefix(root()).unwrap_or(source).display(), position)
+        }
+        _ => format!("{}{} (in the snippets)", bundled, position),
+    }
+}
+
+/// Outcome of running one build on an input, `failure` is the verdict code and its description
+struct Run {
+    output: String,
+    failure: Option<(&'static str, String)>,
+}
+
+fn run(problem: &Problem, binary: &Path, input: &Path, limits: &Limits, solution_lines: usize) -> Result<Run, Box<dyn Error>> {
+    let (stdout, stderr) = (binary.with_extension("out"), binary.with_extension("err"));
+    let mut command = Command::new(binary);
+    command.stdin(File::open(input)?).stdout(File::create(&stdout)?).stderr(File::create(&stderr)?);
+    let execution = execute(command, limits)?;
+
+    let stderr = fs::read_to_string(&stderr)?;
+    let failure = execution.verdict(limits, &stderr).map(|verdict| match panic(&stderr) {
+        Some(panic) => {
+            let location = locate(&panic.location, &problem.bundled(), &problem.source(), solution_lines);
+            (verdict.code(), format!("panicked at {}: {}", location, panic.message))
+        }
+        None => (verdict.code(), verdict.to_string()),
+    });
+    Ok(Run { output: fs::read_to_string(&stdout)?, failure })
+}
+
+/// Builds the problem twice, like Kattis does and with overflow checks, and runs both builds on every sample and
+/// generated input. Overflow hidden by the wrapping arithmetic of the release build shows up as a panic of the
+/// checked build or as outputs which differ between the builds.
+pub(super) fn check(problem: &Problem, limits: &Limits) -> Result<(), Box<dyn Error>> {
+    if problem.metadata()?.interactive {
+        return Err(format!("{} is interactive, it can not be checked", problem.name()).into());
+    }
+
+    let mut samples = problem.samples()?;
+    samples.extend(problem.generated()?);
+    if samples.is_empty() {
+        return Err(format!("no samples found in {}", problem.input_dir().display()).into());
+    }
+
+    let binaries: Vec<PathBuf> = vec![problem.compile()?, problem.compile_checked()?];
+    let solution_lines = fs::read_to_string(problem.source())?.lines().count();
+
+    println!("{:<20} {:<8} {:<8}", "input", "release", "checked");
+
+    let mut failed = 0;
+    for sample in &samples {
+        let mut runs = Vec::new();
+        for binary in &binaries {
+            runs.push(run(problem, binary, &sample.input, limits, solution_lines)?);
+        }
+        let (release, checked) = (&runs[0], &runs[1]);
+
+        let status = |run: &Run| run.failure.as_ref().map_or("ok", |(code, _)| code);
+        println!("{:<20} {:<8} {:<8}", sample.name, status(release), status(checked));
+
+        let mut problems = Vec::new();
+        for (build, run) in [("release", release), ("checked", checked)] {
+            if let Some((_, failure)) = &run.failure {
+                problems.push(format!("{} build: {}", build, failure));
+            }
+        }
+        if release.failure.is_none() && checked.failure.is_none() {
+            if let Err(e) = Checker::Exact.check(&release.output, &checked.output) {
+                problems.push(format!("the outputs differ, the checked build is expected: {}", e));
+            }
+        }
+
+        if !problems.is_empty() {
+            failed += 1;
+            for problem in problems {
+                println!("    {}", problem.replace('\n', "\n    "));
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("{} of {} inputs failed", failed, samples.len()).into());
+    }
+
+    println!("both builds agree on all {} inputs", samples.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panic_locations() {
+        let bundled = root().join("target/problems/nine.rs");
+        let stderr = format!(
+            "thread 'main' (31977) panicked at {}:37:22:\nattempt to multiply with overflow\nstack backtrace:\n",
+            bundled.display()
+        );
+        let panic = panic(&stderr).unwrap();
+        assert_eq!(panic.message, "attempt to multiply with overflow");
+
+        let source = root().join("src/nine.rs");
+        assert_eq!(locate(&panic.location, &bundled, &source, 50), "src/nine.rs:37:22");
+        assert_eq!(locate(&panic.location, &bundled, &source, 30), format!("{}:37:22 (in the snippets)", bundled.display()));
+        assert_eq!(locate("library/core/src/num/mod.rs:1:1", &bundled, &source, 30), "library/core/src/num/mod.rs:1:1");
+        assert_eq!(super::panic("exit code 1"), None);
+    }
+}
@@ -0,0 +1,40 @@
+use std::error::Error;
+use std::fs;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::problem::{root, Problem};
+
+/// Writes a reproducible input of the given size (a number or `max`) to `input/<problem>/gen/<size>-<seed>.in`. The
+/// generator is registered in the tests of the problem with `judge::generate`, so it runs through `cargo test`.
+pub(super) fn gen(problem: &Problem, size: &str, seed: Option<u64>) -> Result<(), Box<dyn Error>> {
+    let test = format!("{}_generate", problem.name());
+    if !fs::read_to_string(problem.source())?.contains(&format!("fn {}(", test)) {
+        return Err(format!("{} has no {} test, register a generator with crate::judge::generate first", problem.name(), test).into());
+    }
+
+    if size != "max" && size.parse::<usize>().is_err() {
+        return Err(format!("invalid size '{}', expected a number or max", size).into());
+    }
+
+    let seed = match seed {
+        Some(seed) => seed,
+        None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as u64,
+    };
+
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--release", "--manifest-path"])
+        .arg(root().join("Cargo.toml"))
+        .arg(&test)
+        .args(["--", "--nocapture"])
+        .env("KATTIS_GEN_SIZE", size)
+        .env("KATTIS_GEN_SEED", seed.to_string())
+        .status()
+        .map_err(|e| format!("could not start cargo: {}", e))?;
+
+    if !status.success() {
+        return Err(format!("generating an input for {} failed", problem.name()).into());
+    }
+
+    Ok(())
+}
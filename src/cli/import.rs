@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::new::{new, Template};
+use crate::problem::{Problem, Sample};
+use crate::zip::{self, Entry};
+
+/// A sample from the archive, the answer is missing for interactive problems
+#[derive(Debug, Clone, PartialEq)]
+struct Case {
+    name: String,
+    input: Vec<u8>,
+    answer: Option<Vec<u8>>,
+}
+
+/// Extracts the samples of a problem into its input directory and creates the solution if it does not exist yet.
+/// Without a zip file the samples are downloaded from open.kattis.com, extracting does not need any external tool.
+pub(super) fn import(problem: &Problem, zip: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let zip = match zip {
+        Some(path) => PathBuf::from(path),
+        None => download(problem)?,
+    };
+
+    let data = fs::read(&zip).map_err(|e| format!("could not read {}: {}", zip.display(), e))?;
+    let entries = zip::read(&data).map_err(|e| format!("could not extract {}: {}", zip.display(), e))?;
+    let cases = cases(entries)?;
+    if cases.is_empty() {
+        return Err(format!("{} does not contain any .in files", zip.display()).into());
+    }
+
+    if !problem.source().exists() {
+        new(problem, Template::find("batch")?)?;
+    }
+
+    let existing = problem.samples()?;
+    let mut added = 0;
+    for imported in add(&problem.input_dir(), &existing, cases)? {
+        match imported {
+            Imported::Added(_) => added += 1,
+            Imported::Duplicate { case, sample } => println!("skipped {}, it is the same as sample {}", case, sample),
+        }
+    }
+    println!("imported {} samples into {}", added, problem.input_dir().display());
+
+    Ok(())
+}
+
+/// Rejects names which would be written outside of the input directory
+fn check_name(name: &str) -> Result<(), String> {
+    let absolute = name.starts_with('/') || name.contains('\\') || name.contains(':');
+    if name.is_empty() || absolute || name.split('/').any(|c| c == "..") {
+        return Err(format!("the archive contains the unsafe path '{}'", name));
+    }
+    Ok(())
+}
+
+/// Pairs the `.in` and `.ans` files of the archive, directories inside the archive are flattened and other files are
+/// ignored
+fn cases(entries: Vec<Entry>) -> Result<Vec<Case>, String> {
+    for entry in &entries {
+        check_name(&entry.name)?;
+    }
+
+    let mut inputs = BTreeMap::new();
+    let mut answers = BTreeMap::new();
+    for entry in entries {
+        if let Some(stem) = entry.name.strip_suffix(".in") {
+            inputs.insert(stem.to_string(), entry.data);
+        } else if let Some(stem) = entry.name.strip_suffix(".ans") {
+            answers.insert(stem.to_string(), entry.data);
+        }
+    }
+
+    Ok(inputs
+        .into_iter()
+        .map(|(stem, input)| Case {
+            name: stem.rsplit('/').next().unwrap().to_string(),
+            answer: answers.remove(&stem),
+            input,
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Imported {
+    /// The case is written under this name
+    Added(String),
+    /// The case is skipped since it is the same as an existing sample
+    Duplicate { case: String, sample: String },
+}
+
+/// Writes the cases which are not a sample yet to `dir`, a case whose name is taken gets a suffix
+fn add(dir: &Path, existing: &[Sample], cases: Vec<Case>) -> Result<Vec<Imported>, Box<dyn Error>> {
+    let mut contents = Vec::new();
+    for sample in existing {
+        let answer = match &sample.answer {
+            Some(answer) => Some(fs::read(answer)?),
+            None => None,
+        };
+        contents.push((sample.name.clone(), fs::read(&sample.input)?, answer));
+    }
+
+    fs::create_dir_all(dir)?;
+    let mut imported = Vec::new();
+    for case in cases {
+        if let Some((name, _, _)) = contents.iter().find(|(_, input, answer)| *input == case.input && *answer == case.answer) {
+            imported.push(Imported::Duplicate { case: case.name, sample: name.clone() });
+            continue;
+        }
+
+        let taken = |name: &str| dir.join(format!("{}.in", name)).exists() || dir.join(format!("{}.ans", name)).exists();
+        let name = if taken(&case.name) {
+            (2..).map(|i| format!("{}-{}", case.name, i)).find(|name| !taken(name)).unwrap()
+        } else {
+            case.name.clone()
+        };
+
+        fs::write(dir.join(format!("{}.in", name)), &case.input)?;
+        if let Some(answer) = &case.answer {
+            fs::write(dir.join(format!("{}.ans", name)), answer)?;
+        }
+        contents.push((name.clone(), case.input, case.answer));
+        imported.push(Imported::Added(name));
+    }
+
+    Ok(imported)
+}
+
+fn download(problem: &Problem) -> Result<PathBuf, Box<dyn Error>> {
+    let zip = std::env::temp_dir().join(format!("kattis-{}.zip", problem.name()));
+    let url = format!("https://open.kattis.com/problems/{}/file/statement/samples.zip", problem.name());
+
+    let status = Command::new("wget")
+        .arg(&url)
+        .arg("-O")
+        .arg(&zip)
+        .status()
+        .map_err(|e| format!("could not start wget: {}, download {} yourself and pass it to import", e, url))?;
+
+    if !status.success() {
+        return Err(format!("could not download {}", url).into());
+    }
+
+    Ok(zip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, data: &str) -> Entry {
+        Entry { name: name.to_string(), data: data.as_bytes().to_vec() }
+    }
+
+    fn case(name: &str, input: &str, answer: Option<&str>) -> Case {
+        Case { name: name.to_string(), input: input.as_bytes().to_vec(), answer: answer.map(|a| a.as_bytes().to_vec()) }
+    }
+
+    #[test]
+    fn unsafe_paths() {
+        for name in &["../1.in", "a/../../1.in", "/etc/passwd", "..\\1.in", "C:1.in", ""] {
+            assert!(cases(vec![entry("1.in", "1"), entry(name, "2")]).is_err(), "{}", name);
+        }
+        assert!(cases(vec![entry("samples/..in", "1"), entry("a..b.in", "2")]).is_ok());
+    }
+
+    #[test]
+    fn pair_cases() {
+        let entries = vec![entry("s/2.ans", "4"), entry("s/2.in", "2"), entry("1.in", "1"), entry("README", ""), entry("3.ans", "9")];
+        assert_eq!(cases(entries).unwrap(), vec![case("1", "1", None), case("2", "2", Some("4"))]);
+    }
+
+    #[test]
+    fn add_new_cases() {
+        let dir = std::env::temp_dir().join(format!("kattis-import-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("1.in"), "1").unwrap();
+        fs::write(dir.join("1.ans"), "2").unwrap();
+        let existing = vec![Sample { name: "1".to_string(), input: dir.join("1.in"), answer: Some(dir.join("1.ans")) }];
+
+        let cases = vec![case("1", "1", Some("2")), case("1", "3", Some("4")), case("2", "3", Some("4")), case("3", "5", None)];
+        let imported = add(&dir, &existing, cases).unwrap();
+        let read = |name: &str| fs::read_to_string(dir.join(name)).ok();
+        let files = vec![read("1-2.in"), read("1-2.ans"), read("3.in"), read("3.ans")];
+        fs::remove_dir_all(&dir).ok();
+
+        let duplicate = |case: &str, sample: &str| Imported::Duplicate { case: case.to_string(), sample: sample.to_string() };
+        assert_eq!(
+            imported,
+            vec![duplicate("1", "1"), Imported::Added("1-2".to_string()), duplicate("2", "1-2"), Imported::Added("3".to_string())]
+        );
+        assert_eq!(files, vec![Some("3".to_string()), Some("4".to_string()), Some("5".to_string()), None]);
+    }
+}
@@ -0,0 +1,49 @@
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fs;
+
+use crate::problem::{root, Problem};
+
+/// Modules of the tool itself and the `default.rs` template, which live in `src/` next to the problems
+const TOOLS: [&str; 10] = ["main", "default", "bundle", "cli", "judge", "metadata", "problem", "snippets", "templates", "zip"];
+
+/// Every file in `src/` with a top-level `main` function is a problem, except for the modules of the tool
+pub(super) fn problems() -> Result<Vec<Problem>, Box<dyn Error>> {
+    let mut problems = Vec::new();
+
+    for entry in fs::read_dir(root().join("src"))? {
+        let path = entry?.path();
+        if path.extension() != Some(OsStr::new("rs")) || TOOLS.iter().any(|tool| path.file_stem().unwrap() == *tool) {
+            continue;
+        }
+
+        // Only a line starting with it counts, tests of the tool contain `fn main(` inside strings
+        if fs::read_to_string(&path)?.lines().any(|l| l.starts_with("fn main(")) {
+            problems.push(Problem::new(&path.file_stem().unwrap().to_string_lossy())?);
+        }
+    }
+
+    problems.sort_by(|a, b| a.name().cmp(b.name()));
+    Ok(problems)
+}
+
+/// Whether `src/main.rs` declares the module of the problem, otherwise its tests never run
+pub(super) fn registered(main: &str, problem: &Problem) -> bool {
+    main.lines().any(|l| l.trim() == format!("mod {};", problem.name()))
+}
+
+pub(super) fn list() -> Result<(), Box<dyn Error>> {
+    let main = fs::read_to_string(root().join("src").join("main.rs"))?;
+
+    println!("{:<32} {:>10} {:>8}", "problem", "registered", "samples");
+    for problem in problems()? {
+        println!(
+            "{:<32} {:>10} {:>8}",
+            problem.name(),
+            if registered(&main, &problem) { "yes" } else { "no" },
+            problem.samples()?.len()
+        );
+    }
+
+    Ok(())
+}
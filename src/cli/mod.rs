@@ -0,0 +1,179 @@
+//! The `kattis` command line helper, used to create, run and test problems.
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::judge::Limits;
+use crate::problem::Problem;
+
+mod bench;
+mod bundle;
+mod check;
+mod gen;
+mod import;
+mod list;
+mod new;
+mod report;
+mod run;
+mod search;
+mod stress;
+mod test;
+mod validate;
+mod watch;
+
+const USAGE: &str = "Usage: kattis <command> [arguments]
+
+Commands:
+    (n)ew <problem> [--template t]   create src/<problem>.rs from the template, register it in src/main.rs and
+                                     create input/<problem>/problem.toml
+    (r)un <problem> [input file]     compile the problem like Kattis does and run it on stdin or the given file
+    (t)est <problem> [--checker c]   compile the problem like Kattis does and run it on all samples, the output is
+                                     judged by the checker: exact, tokens, case-insensitive, float, float:<tolerance>
+                                     or float:<abs>:<rel>, by default the checker of input/<problem>/problem.toml
+    check <problem>                  build the problem like Kattis does and with overflow checks, run both builds on
+                                     all samples and generated inputs and report panics and differing outputs
+    (w)atch <problem>                rerun the samples every time src/<problem>.rs, the snippets or the samples change,
+                                     compile errors are shown without stopping
+    (s)tress <problem>               compare the solution with its brute force on random inputs until they differ,
+                                     the failing input is saved to input/<problem>/stress-N.in
+    bench <problem>                  run the release build several times on the generated inputs (or the samples)
+                                     and compare the CPU times with the history in input/<problem>/bench.tsv
+    (g)en <problem>                  write a random input of the generator of the problem to
+                                     input/<problem>/gen/<size>-<seed>.in
+    validate [problem]               check every .in file of the problem (or of all problems with a validator)
+                                     against the constraints of the problem
+    (b)undle <problem> [output file] print (or write) the solution with the snippets it uses inlined, this is the
+                                     file to submit to Kattis
+    (i)mport <problem> [samples.zip] extract the samples of the given (or downloaded) zip file into input/<problem>/,
+                                     skipping samples which exist already, and create the problem
+    (l)ist                           list all problems with their sample count
+    search <term> [+ <term>...]      find the problems matching every term, a term is a tag (tag:<tag>), an identifier
+                                     the solution uses (uses:<identifier>) or either, e.g. `search BinaryHeap +
+                                     dijkstra`. The functions using the identifiers are shown with their line numbers
+    report [output directory]        write report.md and report.json with the registration, samples, last test
+                                     verdict, line count and tags of every problem, to target/report/ by default
+
+Options of new:
+    --template <name>                batch (src/default.rs, the default), multi (a count of test cases), eof (cases
+                                     until the end of the input or a 0), interactive (with a judge for cargo test) or
+                                     geometry (using snippets::geometry with the float checker)
+
+Options of run, test and check, by default the limits of input/<problem>/problem.toml are used:
+    --time-limit <seconds>           CPU time limit, 1 second without a problem.toml
+    --memory-limit <MB>              memory limit, 1024 MB without a problem.toml
+
+Options of bench:
+    --runs <n>                       runs per input, 5 by default
+    --threshold <percent>            a median this much slower than the best one is a regression, 10 by default
+
+Options of gen:
+    --size <n|max>                   size passed to the generator, max by default
+    --seed <n>                       seed of the input, random by default
+
+Options of stress:
+    --iterations <n>                 amount of random inputs, 1000 by default
+    --seed <n>                       seed of the first input, random by default
+    --size <n>                       maximum size passed to the generator, 10 by default";
+
+/// Entry point of the helper, returns the exit code of the process
+pub fn main(args: Vec<String>) -> i32 {
+    match execute(&args) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
+
+fn execute(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let command = match args.first() {
+        Some(command) => command.as_str(),
+        None => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+    };
+    let args = &args[1..];
+
+    match command {
+        "new" | "n" => {
+            let template = option::<String>(args, "--template")?.unwrap_or_else(|| "batch".to_string());
+            new::new(&problem(args)?, new::Template::find(&template)?)
+        }
+        "run" | "r" => {
+            let problem = problem(args)?;
+            run::run(&problem, args.get(1).filter(|a| !a.starts_with("--")).map(String::as_str), &limits(&problem, args)?)
+        }
+        "test" | "t" => {
+            let problem = problem(args)?;
+            let checker = match option(args, "--checker")? {
+                Some(checker) => checker,
+                None => problem.metadata()?.checker,
+            };
+            test::test(&problem, checker, &limits(&problem, args)?)
+        }
+        "check" => {
+            let problem = problem(args)?;
+            check::check(&problem, &limits(&problem, args)?)
+        }
+        "watch" | "w" => watch::watch(&problem(args)?),
+        "stress" | "s" => stress::stress(
+            &problem(args)?,
+            option(args, "--iterations")?.unwrap_or(1000),
+            option(args, "--seed")?,
+            option(args, "--size")?.unwrap_or(10),
+        ),
+        "bench" => bench::bench(&problem(args)?, option(args, "--runs")?.unwrap_or(5), option(args, "--threshold")?.unwrap_or(10.0)),
+        "gen" | "g" => gen::gen(&problem(args)?, &option::<String>(args, "--size")?.unwrap_or_else(|| "max".to_string()), option(args, "--seed")?),
+        "validate" => match args.first() {
+            Some(_) => validate::validate(Some(&problem(args)?)),
+            None => validate::validate(None),
+        },
+        "bundle" | "b" => bundle::bundle(&problem(args)?, args.get(1).map(String::as_str)),
+        "import" | "i" | "download" | "d" => import::import(&problem(args)?, args.get(1).map(String::as_str)),
+        "list" | "l" | "ls" => list::list(),
+        "search" => search::search(args),
+        "report" => report::report(args.first().map(String::as_str)),
+        "help" | "-h" | "--help" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(format!("unknown command '{}'\n\n{}", command, USAGE).into()),
+    }
+}
+
+/// Parses the value following `name`, if the option is given
+fn option<T: FromStr>(args: &[String], name: &str) -> Result<Option<T>, Box<dyn Error>>
+where
+    T::Err: fmt::Display,
+{
+    match args.iter().position(|arg| arg == name) {
+        Some(i) => match args.get(i + 1) {
+            Some(value) => value.parse().map(Some).map_err(|e| format!("invalid value for {}: {}", name, e).into()),
+            None => Err(format!("missing value for {}", name).into()),
+        },
+        None => Ok(None),
+    }
+}
+
+/// The limits of the problem metadata, overridden by the options
+fn limits(problem: &Problem, args: &[String]) -> Result<Limits, Box<dyn Error>> {
+    let mut limits = problem.metadata()?.limits;
+    if let Some(seconds) = option::<f64>(args, "--time-limit")? {
+        limits.time = Duration::from_secs_f64(seconds);
+    }
+    if let Some(megabytes) = option::<u64>(args, "--memory-limit")? {
+        limits.memory = megabytes * 1024 * 1024;
+    }
+    Ok(limits)
+}
+
+/// The problem is always the first argument of a command
+fn problem(args: &[String]) -> Result<Problem, Box<dyn Error>> {
+    match args.first() {
+        Some(name) => Problem::new(name),
+        None => Err(format!("missing problem name\n\n{}", USAGE).into()),
+    }
+}
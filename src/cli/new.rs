@@ -0,0 +1,138 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::metadata;
+use crate::problem::{root, Problem};
+
+/// A template of `kattis new --template`, the templates are described in the usage and in `src/templates/`
+#[derive(Debug)]
+pub(super) struct Template {
+    pub name: &'static str,
+    /// Checker written to the `problem.toml` of the new problem
+    checker: &'static str,
+    interactive: bool,
+}
+
+const TEMPLATES: [Template; 5] = [
+    Template { name: "batch", checker: "exact", interactive: false },
+    Template { name: "multi", checker: "exact", interactive: false },
+    Template { name: "eof", checker: "exact", interactive: false },
+    Template { name: "interactive", checker: "exact", interactive: true },
+    Template { name: "geometry", checker: "float", interactive: false },
+];
+
+impl Template {
+    pub fn find(name: &str) -> Result<&'static Template, Box<dyn Error>> {
+        TEMPLATES.iter().find(|t| t.name == name).ok_or_else(|| {
+            let names: Vec<&str> = TEMPLATES.iter().map(|t| t.name).collect();
+            format!("unknown template '{}', expected one of {}", name, names.join(", ")).into()
+        })
+    }
+
+    fn path(&self) -> PathBuf {
+        match self.name {
+            "batch" => root().join("src").join("default.rs"),
+            name => root().join("src").join("templates").join(format!("{}.rs", name)),
+        }
+    }
+}
+
+/// Creates the solution from the template, the input directory with a `problem.toml` and registers the module in
+/// `src/main.rs`
+pub(super) fn new(problem: &Problem, template: &Template) -> Result<(), Box<dyn Error>> {
+    let source = problem.source();
+    if source.exists() {
+        return Err(format!("{} already exists", source.display()).into());
+    }
+
+    let text = fs::read_to_string(template.path())?;
+    fs::write(&source, instantiate(&text, problem.name()))?;
+    fs::create_dir_all(problem.input_dir())?;
+    if !problem.metadata_path().exists() {
+        fs::write(problem.metadata_path(), metadata::template(problem.name(), template.checker, template.interactive))?;
+    }
+    register(problem)?;
+
+    println!("created {} from the {} template", source.display(), template.name);
+    Ok(())
+}
+
+/// Ignores the tests of the templates in `src/templates/`, which are compiled by `cargo test` but can only pass once
+/// they belong to a problem
+const IGNORED_TEMPLATE_TEST: &str = "#[ignore = \"a template, its test runs once `kattis new` made it a problem\"]";
+
+/// The solution of a new problem: the template renamed to the problem, with its tests no longer ignored
+fn instantiate(template: &str, name: &str) -> String {
+    let text: String = template.lines().filter(|l| l.trim() != IGNORED_TEMPLATE_TEST).map(|l| format!("{}\n", l)).collect();
+    rename(&text, "default", name)
+}
+
+/// Replaces every occurrence of `from` which is not part of a longer word or a path, so `default_sampleinputs` and
+/// `input/default` are renamed but `Default::default()` or `defaults` are not.
+fn rename(template: &str, from: &str, to: &str) -> String {
+    let mut res = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(i) = rest.find(from) {
+        let before = rest[..i].chars().next_back();
+        let after = rest[i + from.len()..].chars().next();
+
+        res.push_str(&rest[..i]);
+        if before.is_none_or(|c| !c.is_alphanumeric() && c != ':' && c != '.') && after.is_none_or(|c| !c.is_alphanumeric() && c != '(') {
+            res.push_str(to);
+        } else {
+            res.push_str(from);
+        }
+        rest = &rest[i + from.len()..];
+    }
+
+    res.push_str(rest);
+    res
+}
+
+/// Adds `mod <problem>;` after the last problem module in `src/main.rs`
+pub(super) fn register(problem: &Problem) -> Result<(), Box<dyn Error>> {
+    let path = root().join("src").join("main.rs");
+    let main = fs::read_to_string(&path)?;
+    let line = format!("mod {};", problem.name());
+
+    if main.lines().any(|l| l.trim() == line) {
+        return Ok(());
+    }
+
+    // The problem modules are the first block of lines in main.rs
+    let mut lines: Vec<&str> = main.lines().collect();
+    let end = lines.iter().position(|l| !l.starts_with("mod ")).unwrap_or(lines.len());
+    lines.insert(end, &line);
+
+    fs::write(&path, lines.join("\n") + "\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rename_only_whole_words() {
+        assert_eq!(
+            rename("fn default_sampleinputs() { read_dir(\"input/default\"); Default::default(); defaults }", "default", "doctor_kattis"),
+            "fn doctor_kattis_sampleinputs() { read_dir(\"input/doctor_kattis\"); Default::default(); defaults }"
+        );
+    }
+
+    #[test]
+    fn templates() {
+        for template in &TEMPLATES {
+            let source = fs::read_to_string(template.path()).unwrap();
+            assert_eq!(source.contains(IGNORED_TEMPLATE_TEST), template.name != "batch", "{}", template.name);
+            let text = instantiate(&source, "doctor_kattis");
+            assert!(!text.contains("\"default\"") && !text.contains("#[ignore"), "{}", template.name);
+            let test = if template.interactive { "fn doctor_kattis_interactive()" } else { "fn doctor_kattis_sampleinputs()" };
+            assert!(text.contains(test) && text.contains("(\"doctor_kattis\""), "{}", template.name);
+        }
+        assert!(Template::find("geometry").is_ok());
+        assert!(Template::find("nope").unwrap_err().to_string().ends_with("batch, multi, eof, interactive, geometry"));
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::list::{problems, registered};
+use super::test::{last_verdict, LastVerdict};
+use crate::problem::{root, Problem};
+
+/// The state of a single problem, problems with samples but without a solution have no line count
+#[derive(Debug, Clone, PartialEq)]
+struct Row {
+    name: String,
+    title: Option<String>,
+    registered: bool,
+    samples: usize,
+    verdict: Option<LastVerdict>,
+    lines: Option<usize>,
+    difficulty: Option<f64>,
+    tags: Vec<String>,
+}
+
+/// Every problem with a solution in `src/` or samples in `input/`, except for the samples of the template
+fn rows() -> Result<Vec<Row>, Box<dyn Error>> {
+    let mut names: BTreeSet<String> = problems()?.iter().map(|p| p.name().to_string()).collect();
+    for entry in fs::read_dir(root().join("input"))? {
+        let path = entry?.path();
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        if name != "default" && (path.is_dir() || path.extension().is_some_and(|e| e == "in")) {
+            names.insert(name);
+        }
+    }
+
+    let main = fs::read_to_string(root().join("src").join("main.rs"))?;
+    let mut rows = Vec::new();
+    for name in names {
+        let problem = Problem::new(&name)?;
+        let metadata = problem.metadata()?;
+        let lines = match fs::read_to_string(problem.source()) {
+            Ok(source) => Some(source.lines().count()),
+            Err(_) => None,
+        };
+
+        rows.push(Row {
+            registered: registered(&main, &problem),
+            samples: problem.samples()?.len(),
+            verdict: last_verdict(&problem),
+            lines,
+            title: metadata.title,
+            difficulty: metadata.difficulty,
+            tags: metadata.tags,
+            name,
+        });
+    }
+
+    Ok(rows)
+}
+
+fn summary(rows: &[Row]) -> String {
+    let count = |f: &dyn Fn(&Row) -> bool| rows.iter().filter(|r| f(r)).count();
+    format!(
+        "{} problems, {} registered, {} accepted and {} failed on their last test, {} never tested",
+        rows.len(),
+        count(&|r| r.registered),
+        count(&|r| r.verdict.as_ref().is_some_and(|v| v.code == "AC")),
+        count(&|r| r.verdict.as_ref().is_some_and(|v| v.code != "AC" && v.code != "-")),
+        count(&|r| r.verdict.is_none()),
+    )
+}
+
+fn markdown(rows: &[Row]) -> String {
+    let mut res = format!("# Kattis progress\n\n{}\n\n", summary(rows));
+    res.push_str("| Problem | Registered | Samples | Last test | Lines | Tags |\n");
+    res.push_str("|---|---|---:|---|---:|---|\n");
+
+    // A `|` would end the cell early
+    let cell = |s: &str| s.replace('|', "\\|");
+    for row in rows {
+        res.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            cell(&row.name),
+            if row.registered { "yes" } else { "no" },
+            row.samples,
+            row.verdict.as_ref().map_or("-", |v| &v.code),
+            row.lines.map_or("-".to_string(), |l| l.to_string()),
+            cell(&row.tags.join(", "))
+        ));
+    }
+    res
+}
+
+fn json_string(s: &str) -> String {
+    let mut res = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\t' => res.push_str("\\t"),
+            c if (c as u32) < 0x20 => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+fn json(rows: &[Row], timestamp: u64) -> String {
+    let null = || "null".to_string();
+    let problems: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let fields = vec![
+                format!("\"name\": {}", json_string(&row.name)),
+                format!("\"title\": {}", row.title.as_deref().map_or_else(null, json_string)),
+                format!("\"registered\": {}", row.registered),
+                format!("\"samples\": {}", row.samples),
+                format!("\"verdict\": {}", row.verdict.as_ref().map_or_else(null, |v| json_string(&v.code))),
+                format!("\"tested_at\": {}", row.verdict.as_ref().map_or_else(null, |v| v.timestamp.to_string())),
+                format!("\"lines\": {}", row.lines.map_or_else(null, |l| l.to_string())),
+                format!("\"difficulty\": {}", row.difficulty.map_or_else(null, |d| d.to_string())),
+                format!("\"tags\": [{}]", row.tags.iter().map(|t| json_string(t)).collect::<Vec<_>>().join(", ")),
+            ];
+            format!("    {{{}}}", fields.join(", "))
+        })
+        .collect();
+
+    format!("{{\n  \"generated_at\": {},\n  \"problems\": [\n{}\n  ]\n}}\n", timestamp, problems.join(",\n"))
+}
+
+/// Writes `report.md` and `report.json` with the state of every problem to `dir`, `target/report/` by default
+pub(super) fn report(dir: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let dir = dir.map_or_else(|| root().join("target").join("report"), PathBuf::from);
+    let rows = rows()?;
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("report.md"), markdown(&rows))?;
+    fs::write(dir.join("report.json"), json(&rows, timestamp))?;
+
+    println!("{}", summary(&rows));
+    println!("wrote {} and {}", dir.join("report.md").display(), dir.join("report.json").display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats() {
+        let rows = vec![
+            Row {
+                name: "rafting".to_string(),
+                title: Some("Rafting \"2\"".to_string()),
+                registered: true,
+                samples: 2,
+                verdict: Some(LastVerdict { timestamp: 7, code: "AC".to_string() }),
+                lines: Some(80),
+                difficulty: Some(3.4),
+                tags: vec!["geometry".to_string(), "a|b".to_string()],
+            },
+            Row { name: "new".to_string(), title: None, registered: false, samples: 0, verdict: None, lines: None, difficulty: None, tags: vec![] },
+        ];
+
+        let markdown = markdown(&rows);
+        assert!(markdown.contains("2 problems, 1 registered, 1 accepted and 0 failed on their last test, 1 never tested"));
+        assert!(markdown.contains("| rafting | yes | 2 | AC | 80 | geometry, a\\|b |\n| new | no | 0 | - | - |  |\n"));
+
+        assert_eq!(
+            json(&rows, 42),
+            "{\n  \"generated_at\": 42,\n  \"problems\": [\n    {\"name\": \"rafting\", \"title\": \"Rafting \\\"2\\\"\", \
+             \"registered\": true, \"samples\": 2, \"verdict\": \"AC\", \"tested_at\": 7, \"lines\": 80, \"difficulty\": 3.4, \
+             \"tags\": [\"geometry\", \"a|b\"]},\n    {\"name\": \"new\", \"title\": null, \"registered\": false, \"samples\": 0, \
+             \"verdict\": null, \"tested_at\": null, \"lines\": null, \"difficulty\": null, \"tags\": []}\n  ]\n}\n"
+        );
+        assert_eq!(json_string("a\u{1}\n"), "\"a\\u0001\\n\"");
+    }
+
+    #[test]
+    fn only_problems() {
+        let names: Vec<String> = rows().unwrap().into_iter().map(|row| row.name).collect();
+        for tool in &["bundle", "cli", "judge", "snippets"] {
+            assert!(!names.iter().any(|name| name == tool), "{} is reported as a problem", tool);
+        }
+        assert!(names.iter().any(|name| name == "water"));
+    }
+}
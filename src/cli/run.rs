@@ -0,0 +1,32 @@
+use std::error::Error;
+use std::fs::File;
+use std::process::{Command, Stdio};
+
+use crate::judge::{execute, megabytes, Limits};
+use crate::problem::Problem;
+
+/// Runs the problem within the limits on stdin or the given input file, the resource usage is reported afterwards
+pub(super) fn run(problem: &Problem, input: Option<&str>, limits: &Limits) -> Result<(), Box<dyn Error>> {
+    let binary = problem.compile()?;
+
+    let stdin = match input {
+        Some(path) => Stdio::from(File::open(path).map_err(|e| format!("could not open {}: {}", path, e))?),
+        None => Stdio::inherit(),
+    };
+
+    let mut command = Command::new(&binary);
+    command.stdin(stdin);
+    let execution = execute(command, limits)?;
+
+    eprintln!(
+        "wall {:.3}s, cpu {:.3}s, memory {}",
+        execution.wall.as_secs_f64(),
+        execution.cpu.as_secs_f64(),
+        megabytes(execution.peak_rss)
+    );
+
+    match execution.verdict(limits, "") {
+        Some(verdict) => Err(verdict.to_string().into()),
+        None => Ok(()),
+    }
+}
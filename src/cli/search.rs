@@ -0,0 +1,199 @@
+use std::error::Error;
+use std::fs;
+
+use super::list::problems;
+use crate::bundle::code_tokens;
+use crate::problem::root;
+
+/// A single part of a query, terms are separated by `+`
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    /// `tag:<tag>`, a tag of `problem.toml` containing the text
+    Tag(String),
+    /// `uses:<identifier>`, an identifier used by the solution
+    Uses(String),
+    /// Either a tag or an identifier
+    Any(String),
+}
+
+impl Term {
+    fn parse(term: &str) -> Result<Self, String> {
+        let term = term.trim();
+        let (term, text) = match term.split_once(':') {
+            Some(("tag", text)) => (Term::Tag(text.trim().to_lowercase()), text),
+            Some(("uses", text)) => (Term::Uses(text.trim().to_lowercase()), text),
+            _ => (Term::Any(term.to_lowercase()), term),
+        };
+        if text.trim().is_empty() {
+            return Err("empty search term".to_string());
+        }
+        Ok(term)
+    }
+
+    fn matches_tag(&self, tags: &[String]) -> bool {
+        match self {
+            Term::Tag(text) | Term::Any(text) => tags.iter().any(|tag| tag.to_lowercase().contains(text.as_str())),
+            Term::Uses(_) => false,
+        }
+    }
+
+    /// Identifiers match case-insensitively, either completely or one of their `_` separated words, so `dijkstra`
+    /// finds both `dijkstra` and `inner_dijkstra`
+    fn matches_ident(&self, ident: &str) -> bool {
+        match self {
+            Term::Uses(text) | Term::Any(text) => {
+                let ident = ident.to_lowercase();
+                ident == *text || ident.split('_').any(|word| word == text)
+            }
+            Term::Tag(_) => false,
+        }
+    }
+}
+
+/// Name and line of a function, `None` outside of functions
+type Function = Option<(String, usize)>;
+
+/// An identifier matching a term, with the function it is used in
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Use {
+    /// The innermost function around the identifier
+    function: Function,
+    ident: String,
+    line: usize,
+}
+
+fn uses(source: &str, term: &Term) -> Vec<Use> {
+    let line_starts: Vec<usize> = std::iter::once(0).chain(source.match_indices('\n').map(|(i, _)| i + 1)).collect();
+    let line = |offset: usize| line_starts.partition_point(|&start| start <= offset);
+
+    // Functions whose body is open, with the brace depth inside their body
+    let mut functions: Vec<(String, usize, usize)> = Vec::new();
+    let (mut depth, mut pending, mut after_fn) = (0, None, false);
+
+    let mut res = Vec::new();
+    for (offset, token) in code_tokens(source) {
+        match token {
+            "{" => {
+                depth += 1;
+                if let Some((name, line)) = pending.take() {
+                    functions.push((name, line, depth));
+                }
+            }
+            "}" => {
+                depth -= 1;
+                while functions.last().is_some_and(|&(_, _, d)| d > depth) {
+                    functions.pop();
+                }
+            }
+            // A function without a body, as in a trait
+            ";" => pending = None,
+            _ => {}
+        }
+
+        if after_fn {
+            pending = Some((token.to_string(), line(offset)));
+        }
+        if token.starts_with(|c: char| c.is_alphabetic() || c == '_') && term.matches_ident(token) {
+            // The name of a function belongs to the function itself
+            let function = match &pending {
+                Some(pending) => Some(pending.clone()),
+                None => functions.last().map(|(name, line, _)| (name.clone(), *line)),
+            };
+            res.push(Use { function, ident: token.to_string(), line: line(offset) });
+        }
+        after_fn = token == "fn";
+    }
+    res
+}
+
+/// Prints the problems matching every term of the query, with the functions using the searched identifiers
+pub(super) fn search(query: &[String]) -> Result<(), Box<dyn Error>> {
+    let terms = query.join(" ").split('+').map(Term::parse).collect::<Result<Vec<_>, _>>()?;
+
+    let mut found = 0;
+    for problem in problems()? {
+        let tags = problem.metadata()?.tags;
+        let source = fs::read_to_string(problem.source())?;
+
+        let mut all = Vec::new();
+        let mut matches = true;
+        for term in &terms {
+            let uses = uses(&source, term);
+            matches &= term.matches_tag(&tags) || !uses.is_empty();
+            all.extend(uses);
+        }
+        if !matches {
+            continue;
+        }
+
+        found += 1;
+        println!("{} [{}]", problem.name(), tags.join(", "));
+        let source = problem.source();
+        let path = source.strip_prefix(root()).unwrap_or(&source);
+
+        // Group the uses per function, in order of appearance
+        all.sort_by_key(|u| u.line);
+        let mut functions: Vec<(&Function, Vec<&Use>)> = Vec::new();
+        for u in &all {
+            match functions.iter_mut().find(|(function, _)| **function == u.function) {
+                Some((_, uses)) => uses.push(u),
+                None => functions.push((&u.function, vec![u])),
+            }
+        }
+        for (function, uses) in functions {
+            let (name, line) = match function {
+                Some((name, line)) => (format!("fn {}", name), *line),
+                None => ("top level".to_string(), uses[0].line),
+            };
+            let mut idents: Vec<&str> = uses.iter().map(|u| u.ident.as_str()).collect();
+            idents.sort_unstable();
+            idents.dedup();
+            let mut lines: Vec<String> = uses.iter().map(|u| u.line.to_string()).collect();
+            lines.dedup();
+            println!("    {}:{} {} uses {} (line {})", path.display(), line, name, idents.join(", "), lines.join(", "));
+        }
+    }
+
+    if found == 0 {
+        return Err(format!("no problems found for '{}'", query.join(" ")).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terms() {
+        assert_eq!(Term::parse(" tag:Max flow "), Ok(Term::Tag("max flow".to_string())));
+        assert_eq!(Term::parse("uses:BinaryHeap"), Ok(Term::Uses("binaryheap".to_string())));
+        assert_eq!(Term::parse("Dijkstra"), Ok(Term::Any("dijkstra".to_string())));
+        assert!(Term::parse("tag:").is_err());
+
+        let tags = vec!["max flow".to_string()];
+        assert!(Term::Tag("flow".to_string()).matches_tag(&tags));
+        assert!(!Term::Uses("flow".to_string()).matches_tag(&tags));
+        assert!(Term::Any("dijkstra".to_string()).matches_ident("inner_Dijkstra"));
+        assert!(!Term::Any("dijkstra".to_string()).matches_ident("dijkstras"));
+    }
+
+    #[test]
+    fn functions() {
+        let source = "use std::collections::BinaryHeap;\n\nfn dijkstra() {\n    // BinaryHeap\n    let h = BinaryHeap::new();\n    \
+                      if true { fn heap() -> BinaryHeap<u8> { todo!() } }\n    \"BinaryHeap\";\n}\ntype Heap = BinaryHeap<u8>;\n";
+        let function = |name: &str, line| Some((name.to_string(), line));
+        let heap = |function, line| Use { function, ident: "BinaryHeap".to_string(), line };
+        assert_eq!(
+            uses(source, &Term::Any("binaryheap".to_string())),
+            vec![heap(None, 1), heap(function("dijkstra", 3), 5), heap(function("heap", 6), 6), heap(None, 9)]
+        );
+        assert_eq!(
+            uses(source, &Term::Uses("heap".to_string())),
+            vec![
+                Use { function: function("heap", 6), ident: "heap".to_string(), line: 6 },
+                Use { function: None, ident: "Heap".to_string(), line: 9 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use std::error::Error;
+use std::fs;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::problem::{root, Problem};
+
+/// Runs the `<problem>_stress` test of the problem in release mode, the stress harness itself lives in
+/// `judge::stress` so generators and brute force references stay out of the submitted source
+pub(super) fn stress(problem: &Problem, iterations: u64, seed: Option<u64>, size: u64) -> Result<(), Box<dyn Error>> {
+    let test = format!("{}_stress", problem.name());
+    if !fs::read_to_string(problem.source())?.contains(&format!("fn {}(", test)) {
+        return Err(format!("{} has no {} test, add a generator and brute force first", problem.name(), test).into());
+    }
+
+    // A new seed every run finds more bugs, it is printed so a failure can be reproduced with --seed
+    let seed = match seed {
+        Some(seed) => seed,
+        None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as u64,
+    };
+    println!("stress testing {} with {} inputs of size at most {}, seed {}", problem.name(), iterations, size, seed);
+
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--release", "--manifest-path"])
+        .arg(root().join("Cargo.toml"))
+        .arg(&test)
+        .args(["--", "--include-ignored", "--nocapture"])
+        .env("KATTIS_STRESS_ITERATIONS", iterations.to_string())
+        .env("KATTIS_STRESS_SEED", seed.to_string())
+        .env("KATTIS_STRESS_SIZE", size.to_string())
+        .status()
+        .map_err(|e| format!("could not start cargo: {}", e))?;
+
+    if !status.success() {
+        return Err(format!("stress test of {} failed", problem.name()).into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use std::error::Error;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::judge::{execute, megabytes, Checker, Execution, Limits, Verdict};
+use crate::problem::{root, Problem, Sample};
+
+/// The verdict of the last `kattis test` of a problem, as shown by `kattis report`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct LastVerdict {
+    pub timestamp: u64,
+    /// `AC`, the code of the first failed sample, `CE` if the solution did not compile or `-` if nothing was checked
+    pub code: String,
+}
+
+fn verdict_path(problem: &Problem) -> PathBuf {
+    root().join("target").join("verdicts").join(problem.name())
+}
+
+pub(super) fn last_verdict(problem: &Problem) -> Option<LastVerdict> {
+    let text = fs::read_to_string(verdict_path(problem)).ok()?;
+    let (timestamp, code) = text.trim().split_once('\t')?;
+    Some(LastVerdict { timestamp: timestamp.parse().ok()?, code: code.to_string() })
+}
+
+fn save_verdict(problem: &Problem, code: &str) -> Result<(), Box<dyn Error>> {
+    let path = verdict_path(problem);
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, format!("{}\t{}\n", SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(), code))?;
+    Ok(())
+}
+
+/// Runs the binary on the sample within the limits and judges its output, returns the resource usage, the verdict
+/// (`None` if the sample has no answer) and the stderr of the run
+pub(super) fn judge(
+    binary: &Path,
+    sample: &Sample,
+    checker: Checker,
+    limits: &Limits,
+) -> Result<(Execution, Option<Verdict>, String), Box<dyn Error>> {
+    let (stdout, stderr) = (binary.with_extension("out"), binary.with_extension("err"));
+    let mut command = Command::new(binary);
+    command.stdin(File::open(&sample.input)?).stdout(File::create(&stdout)?).stderr(File::create(&stderr)?);
+    let execution = execute(command, limits)?;
+    let stderr = fs::read_to_string(&stderr)?;
+
+    let verdict = match (execution.verdict(limits, &stderr), &sample.answer) {
+        (Some(verdict), _) => Some(verdict),
+        (None, Some(answer)) => match checker.check(&fs::read_to_string(&stdout)?, &fs::read_to_string(answer)?) {
+            Ok(()) => Some(Verdict::Accepted),
+            Err(e) => Some(Verdict::WrongAnswer(e)),
+        },
+        (None, None) => None,
+    };
+    Ok((execution, verdict, stderr))
+}
+
+/// Runs the compiled problem on every sample within the limits and judges the output with the given checker
+pub(super) fn test(problem: &Problem, checker: Checker, limits: &Limits) -> Result<(), Box<dyn Error>> {
+    if problem.metadata()?.interactive {
+        return Err(format!("{} is interactive, its judge runs with `cargo test {}`", problem.name(), problem.name()).into());
+    }
+
+    let samples = problem.samples()?;
+    if samples.is_empty() {
+        return Err(format!("no samples found in {}", problem.input_dir().display()).into());
+    }
+
+    let binary = match problem.compile() {
+        Ok(binary) => binary,
+        Err(e) => {
+            save_verdict(problem, "CE")?;
+            return Err(e);
+        }
+    };
+
+    println!("{:<20} {:<8} {:>8} {:>8} {:>10}", "sample", "verdict", "wall", "cpu", "memory");
+
+    let (mut failed, mut checked) = (0, 0);
+    let mut first_failure = None;
+    for sample in &samples {
+        let (execution, verdict, stderr) = judge(&binary, sample, checker, limits)?;
+
+        println!(
+            "{:<20} {:<8} {:>7.3}s {:>7.3}s {:>10}",
+            sample.name,
+            verdict.as_ref().map_or("-", Verdict::code),
+            execution.wall.as_secs_f64(),
+            execution.cpu.as_secs_f64(),
+            megabytes(execution.peak_rss)
+        );
+
+        match verdict {
+            Some(Verdict::Accepted) => checked += 1,
+            None => println!("    no answer file, output not checked"),
+            Some(verdict) => {
+                failed += 1;
+                first_failure.get_or_insert(verdict.code());
+                match &verdict {
+                    Verdict::WrongAnswer(e) => println!("    wrong answer, {}", e.replace('\n', "\n    ")),
+                    verdict => println!("    {}", verdict),
+                }
+                if !stderr.trim().is_empty() {
+                    println!("    stderr: {}", stderr.trim().replace('\n', "\n            "));
+                }
+            }
+        }
+    }
+
+    save_verdict(problem, first_failure.unwrap_or(if checked > 0 { "AC" } else { "-" }))?;
+    if failed > 0 {
+        return Err(format!("{} of {} samples failed", failed, samples.len()).into());
+    }
+
+    println!("all {} samples passed", samples.len());
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use std::error::Error;
+use std::fs;
+use std::process::Command;
+
+use crate::problem::{root, Problem};
+
+/// Checks every `.in` file of the problem, or of all problems with a validator, against the constraints. The
+/// validators are registered in the tests of the problems with `judge::validate`, so they run through `cargo test`.
+pub(super) fn validate(problem: Option<&Problem>) -> Result<(), Box<dyn Error>> {
+    let filter = match problem {
+        Some(problem) => {
+            let test = format!("{}_validate", problem.name());
+            if !fs::read_to_string(problem.source())?.contains(&format!("fn {}(", test)) {
+                return Err(format!("{} has no {} test, register a validator with crate::judge::validate first", problem.name(), test).into());
+            }
+            test
+        }
+        None => "_validate".to_string(),
+    };
+
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--manifest-path"])
+        .arg(root().join("Cargo.toml"))
+        .arg(&filter)
+        .args(["--", "--nocapture", "--test-threads=1"])
+        .status()
+        .map_err(|e| format!("could not start cargo: {}", e))?;
+
+    if !status.success() {
+        return Err("some inputs do not respect the constraints".into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use super::test::judge;
+use crate::judge::Verdict;
+use crate::problem::{root, Problem};
+
+/// Time between two scans of the watched files
+const INTERVAL: Duration = Duration::from_millis(300);
+
+/// Modification time and size of every watched file, a change in either means the file has been saved
+type Snapshot = Vec<(PathBuf, SystemTime, u64)>;
+
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut files = Vec::new();
+    for path in paths {
+        let entries: Vec<PathBuf> = match fs::read_dir(path) {
+            Ok(dir) => dir.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+            Err(_) => vec![path.clone()],
+        };
+        for entry in entries {
+            if let Ok(metadata) = fs::metadata(&entry) {
+                if metadata.is_file() {
+                    files.push((entry, metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH), metadata.len()));
+                }
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// The files which were added, removed or changed between the snapshots
+fn changes(old: &Snapshot, new: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = old
+        .iter()
+        .filter(|f| !new.contains(f))
+        .chain(new.iter().filter(|f| !old.contains(f)))
+        .map(|f| f.0.clone())
+        .collect();
+    changed.sort();
+    changed.dedup();
+    changed
+}
+
+/// Compiles the problem unless only the samples changed, then runs all samples and prints a single line with the
+/// result. The first failure is described below it.
+fn round(problem: &Problem, binary: &mut Option<PathBuf>, compile: bool) -> Result<(), Box<dyn Error>> {
+    let metadata = problem.metadata()?;
+
+    let mut compile_time = None;
+    if compile || binary.is_none() {
+        let start = Instant::now();
+        *binary = None;
+        match problem.compile() {
+            Ok(path) => *binary = Some(path),
+            Err(e) => {
+                println!("CE  {}", e);
+                return Ok(());
+            }
+        }
+        compile_time = Some(start.elapsed());
+    }
+    let binary = binary.as_ref().unwrap();
+
+    let samples = problem.samples()?;
+    let (mut passed, mut cpu, mut wall) = (0, Duration::ZERO, Duration::ZERO);
+    let mut failure = None;
+    for sample in &samples {
+        let (execution, verdict, _) = judge(binary, sample, metadata.checker, &metadata.limits)?;
+        cpu = cpu.max(execution.cpu);
+        wall += execution.wall;
+        match verdict {
+            Some(Verdict::Accepted) | None => passed += 1,
+            Some(verdict) => {
+                failure.get_or_insert((sample.name.clone(), verdict));
+            }
+        }
+    }
+
+    let code = failure.as_ref().map_or("AC", |(_, verdict)| verdict.code());
+    let compiled = compile_time.map_or(String::new(), |t| format!(", compiled in {:.2}s", t.as_secs_f64()));
+    println!(
+        "{:<3} {}/{} samples passed, max cpu {:.3}s, total wall {:.3}s{}",
+        code,
+        passed,
+        samples.len(),
+        cpu.as_secs_f64(),
+        wall.as_secs_f64(),
+        compiled
+    );
+    if let Some((name, verdict)) = failure {
+        let description = match verdict {
+            Verdict::WrongAnswer(e) => format!("wrong answer, {}", e),
+            verdict => verdict.to_string(),
+        };
+        println!("    sample {}: {}", name, description.replace('\n', "\n    "));
+    }
+    Ok(())
+}
+
+fn relative(path: &Path) -> String {
+    path.strip_prefix(root()).unwrap_or(path).display().to_string()
+}
+
+/// Reruns the samples every time the solution, the snippets or the samples of the problem change, until the
+/// process is interrupted. Only changes of the sources trigger a new compilation.
+pub(super) fn watch(problem: &Problem) -> Result<(), Box<dyn Error>> {
+    if !problem.source().is_file() {
+        return Err(format!("{} does not exist, create it with `kattis new {}`", problem.source().display(), problem.name()).into());
+    }
+    if problem.metadata()?.interactive {
+        return Err(format!("{} is interactive, its judge runs with `cargo test {}`", problem.name(), problem.name()).into());
+    }
+
+    let sources = vec![problem.source(), root().join("src").join("snippets")];
+    let inputs = vec![problem.input_dir(), problem.flat_input()];
+    println!("watching {} and {}, press Ctrl-C to stop", relative(&sources[0]), relative(&inputs[0]));
+
+    let mut binary = None;
+    let (mut old_sources, mut old_inputs) = (snapshot(&sources), snapshot(&inputs));
+    let mut compile = true;
+    loop {
+        if let Err(e) = round(problem, &mut binary, compile) {
+            println!("error: {}", e);
+        }
+
+        // Wait until a file changes, the changes of a save often arrive in several writes
+        let (new_sources, new_inputs) = loop {
+            thread::sleep(INTERVAL);
+            let (new_sources, new_inputs) = (snapshot(&sources), snapshot(&inputs));
+            if new_sources != old_sources || new_inputs != old_inputs {
+                thread::sleep(INTERVAL);
+                break (snapshot(&sources), snapshot(&inputs));
+            }
+        };
+
+        let changed: Vec<String> = changes(&old_sources, &new_sources)
+            .iter()
+            .chain(&changes(&old_inputs, &new_inputs))
+            .map(|path| relative(path))
+            .collect();
+        println!("\nchanged {}", changed.join(", "));
+
+        compile = new_sources != old_sources;
+        old_sources = new_sources;
+        old_inputs = new_inputs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_files() {
+        let time = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let file = |name: &str, secs, len| (PathBuf::from(name), time(secs), len);
+        let old = vec![file("a.in", 1, 10), file("b.in", 1, 10), file("c.in", 1, 10)];
+        let new = vec![file("a.in", 1, 10), file("b.in", 2, 10), file("d.in", 2, 3)];
+        assert_eq!(changes(&old, &new), vec![PathBuf::from("b.in"), PathBuf::from("c.in"), PathBuf::from("d.in")]);
+        assert!(changes(&new, &new).is_empty());
+    }
+}
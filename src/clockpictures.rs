@@ -88,27 +88,56 @@ fn to_delta_vec(mut inp: Vec<u32>) -> Vec<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::judge::Rng;
+    use std::collections::HashSet;
 
     #[test]
     fn clockpictures_sampleinputs() {
-        for mut file in std::fs::read_dir("input/clockpictures")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert_eq!(
-                std::str::from_utf8(&output_writer).unwrap().trim(),
-                std::fs::read_to_string(&file).unwrap().trim(),
-                "file: {:?}",
-                file
-            );
+        crate::judge::test_samples("clockpictures", |input, output| super::solve(input, output));
+    }
+
+    /// Tries every rotation mapping the first hand of the first clock on a hand of the second clock
+    fn brute(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
+        let clocks: Vec<Vec<u32>> = input.lines().skip(1).map(|l| l.unwrap().split(' ').map(|x| x.parse().unwrap()).collect()).collect();
+        let second: HashSet<u32> = clocks[1].iter().copied().collect();
+
+        let possible = clocks[1].iter().any(|&b| {
+            let rotation = (b + 360_000 - clocks[0][0]) % 360_000;
+            clocks[0].iter().all(|a| second.contains(&((a + rotation) % 360_000)))
+        });
+
+        writeln!(&mut w, "{}", if possible { "possible" } else { "impossible" })?;
+        Ok(())
+    }
+
+    /// Two clocks which are often a rotation of each other, with hands on a coarse grid to get symmetric clocks
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let step = *rng.choose(&[1, 1000, 45_000]);
+        let mut first = Vec::new();
+        for _ in 0..size {
+            let hand = rng.range(0..=360_000 / step - 1) * step;
+            if !first.contains(&hand) {
+                first.push(hand);
+            }
         }
+
+        let rotation = rng.range(0..=359_999);
+        let mut second: Vec<u32> = first.iter().map(|a| (a + rotation) % 360_000).collect();
+        if rng.chance(0.5) {
+            let i = rng.range(0..=second.len() - 1);
+            let moved = (second[i] + step) % 360_000;
+            if !second.contains(&moved) {
+                second[i] = moved;
+            }
+        }
+        rng.shuffle(&mut second);
+
+        let line = |hands: &[u32]| hands.iter().map(u32::to_string).collect::<Vec<_>>().join(" ");
+        format!("{}\n{}\n{}\n", first.len(), line(&first), line(&second))
+    }
+
+    #[test]
+    fn clockpictures_stress() {
+        crate::judge::stress("clockpictures", generate, |input, output| solve(input, output), |input, output| brute(input, output));
     }
 }
@@ -2,6 +2,8 @@ use std::error::Error;
 use std::io::{self, BufRead, Write};
 use std::cmp;
 
+use crate::snippets::fixed::Fixed;
+
 /// main entry point of program when invoked directly in the terminal
 #[allow(dead_code)]
 fn main() -> Result<(), Box<dyn Error>> {
@@ -48,36 +50,10 @@ fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// The coordinates have at most two decimals, scaled by 100 they are exact integers
 fn parse_float(inp: &str) -> IntSize {
-    let mut ans: IntSize = 0;
-    let mut minus = false;
-    let mut count = 0;
-    let mut after_dot = false;
-    for c in inp.chars() {
-        match c {
-            '-' => minus = true,
-            '.' => after_dot = true,
-            c => {
-                ans *= 10;
-                ans += c.to_digit(10).unwrap() as IntSize;
-                if after_dot {
-                    count += 1;
-                }
-            }
-        }
-    }
-
-    if count == 0 {
-        ans *= 100;
-    } else if count == 1 {
-        ans *= 10;
-    }
-
-    if minus {
-        -ans
-    } else {
-        ans
-    }
+    let x: Fixed = inp.parse().unwrap();
+    x.rescale(2).expect("a coordinate has more than two decimals").value as IntSize
 }
 
 fn min_distance_full<'a>(points: &'a [Point]) -> (IntSize, &'a Point, &'a Point) {
@@ -139,27 +115,136 @@ fn min_distance<'a>(points: &'a [Point]) -> (IntSize, &'a Point, &'a Point) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::judge::{Input, Rng, ValidationError};
 
     #[test]
     fn closestpair2_sampleinputs() {
-        for mut file in std::fs::read_dir("input/closestpair2")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert_eq!(
-                std::str::from_utf8(&output_writer).unwrap().trim(),
-                std::fs::read_to_string(&file).unwrap().trim(),
-                "file: {:?}",
-                file
-            );
+        crate::judge::test_samples("closestpair2", |input, output| super::solve(input, output));
+    }
+
+    /// Points of every test case as coordinates multiplied by 100 together with the original line
+    fn test_cases(input: &str) -> Vec<Vec<(i64, i64, &str)>> {
+        let scaled = |x: &str| (x.parse::<f64>().unwrap() * 100.0).round() as i64;
+        let mut lines = input.lines();
+        let mut cases = Vec::new();
+
+        while let Some(n) = lines.next().and_then(|l| l.parse::<usize>().ok()).filter(|&n| n > 0) {
+            cases.push((&mut lines).take(n).map(|l| {
+                let mut ps = l.split(' ');
+                (scaled(ps.next().unwrap()), scaled(ps.next().unwrap()), l)
+            }).collect());
+        }
+
+        cases
+    }
+
+    fn dist((ax, ay, _): (i64, i64, &str), (bx, by, _): (i64, i64, &str)) -> i64 {
+        (ax - bx).pow(2) + (ay - by).pow(2)
+    }
+
+    /// Prints the smallest squared distance of every test case, found by comparing all pairs
+    fn brute(mut input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
+        let mut s = String::new();
+        input.read_to_string(&mut s)?;
+
+        for points in test_cases(&s) {
+            let min = (0..points.len())
+                .flat_map(|i| (i + 1..points.len()).map(move |o| (i, o)))
+                .map(|(i, o)| dist(points[i], points[o]))
+                .min()
+                .unwrap();
+            writeln!(&mut w, "{}", min)?;
+        }
+        Ok(())
+    }
+
+    /// Any closest pair is accepted, so only check that the pair exists and has the smallest distance
+    fn compare(input: &str, output: &str, expected: &str) -> Result<(), String> {
+        let cases = test_cases(input);
+        let mut output = output.lines();
+
+        for (case, (points, expected)) in cases.iter().zip(expected.lines()).enumerate() {
+            let line = output.next().ok_or(format!("missing output for test case {}", case + 1))?;
+            let tokens: Vec<&str> = line.split(' ').collect();
+            if tokens.len() != 4 {
+                return Err(format!("test case {}: expected two points, got '{}'", case + 1, line));
+            }
+
+            let (a, b) = (tokens[..2].join(" "), tokens[2..].join(" "));
+            let pa = points.iter().position(|p| p.2 == a);
+            let pb = points.iter().rposition(|p| p.2 == b);
+            match (pa, pb) {
+                (Some(pa), Some(pb)) if pa != pb => {
+                    let d = dist(points[pa], points[pb]);
+                    if d.to_string() != expected {
+                        return Err(format!("test case {}: squared distance of {} is {}, expected {}", case + 1, line, d, expected));
+                    }
+                }
+                _ => return Err(format!("test case {}: '{}' are not two points of the input", case + 1, line)),
+            }
+        }
+
+        match output.next() {
+            Some(line) => Err(format!("trailing output '{}'", line)),
+            None => Ok(()),
+        }
+    }
+
+    /// A few test cases with points close together, written with zero, one or two decimals
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let range = 10 * size as i64;
+        let coordinate = |rng: &mut Rng| {
+            let x = rng.range(-range * 100..=range * 100);
+            let (int, frac) = (x.abs() / 100, x.abs() % 100);
+            let sign = if x < 0 { "-" } else { "" };
+            match rng.range(0..=2) {
+                0 => format!("{}{}", sign, int),
+                1 => format!("{}{}.{}", sign, int, frac / 10),
+                _ => format!("{}{}.{:02}", sign, int, frac),
+            }
+        };
+
+        let mut input = String::new();
+        for _ in 0..rng.range(1..=3) {
+            let n = rng.range(2..=size + 2);
+            input.push_str(&format!("{}\n", n));
+            for _ in 0..n {
+                let (x, y) = (coordinate(rng), coordinate(rng));
+                input.push_str(&format!("{} {}\n", x, y));
+            }
         }
+        input.push_str("0\n");
+        input
+    }
+
+    #[test]
+    fn closestpair2_stress() {
+        crate::judge::stress_with("closestpair2", generate, |input, output| solve(input, output), |input, output| brute(input, output), compare);
     }
-}
\ No newline at end of file
+
+    /// `parse_float` scales the coordinates by 100, so a third decimal is rejected
+    fn validate(input: &str) -> Result<(), ValidationError> {
+        let mut input = Input::new(input);
+        loop {
+            let n = input.line(1)?.int(0..=100_000usize)?;
+            if n == 0 {
+                return input.eof();
+            }
+            input.check(n >= 2, || "a test case should have at least two points".to_string())?;
+
+            for _ in 0..n {
+                let mut line = input.line(2)?;
+                line.float(-1e6..=1e6, 2)?;
+                line.float(-1e6..=1e6, 2)?;
+            }
+        }
+    }
+
+    #[test]
+    fn closestpair2_validate() {
+        crate::judge::validate("closestpair2", validate);
+        for seed in 0..10 {
+            validate(&generate(&mut Rng::new(seed), 10)).unwrap();
+        }
+    }
+}
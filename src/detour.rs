@@ -1,8 +1,9 @@
 use std::error::Error;
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::HashMap;
 use std::io::{self, BufRead, Write};
 
+use crate::snippets::graph::dijkstra;
+
 /// main entry point of program when invoked directly in the terminal
 #[allow(dead_code)]
 fn main() -> Result<(), Box<dyn Error>> {
@@ -23,7 +24,10 @@ fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
         intersections[line[1]].insert(line[0], line[2]);
     }
 
-    let fastest_roads = dijkstra(1, 0, &intersections, false); // Compute fasted road from each intersection; (Ams to Delft)
+    let roads = |intersections: &Vec<HashMap<usize, usize>>| -> Vec<Vec<(usize, usize)>> {
+        intersections.iter().map(|roads| roads.iter().map(|(&to, &cost)| (to, cost)).collect()).collect()
+    };
+    let fastest_roads = dijkstra(&roads(&intersections), &[(1, 0)], None).prev; // Compute fasted road from each intersection; (Ams to Delft)
 
     // Remove road from graph
     for (from, to) in fastest_roads.iter().enumerate() {
@@ -33,22 +37,11 @@ fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
     }
 
     // Calculate shortest path
-    let path = dijkstra(0, 1, &intersections, true);
-
-    match path[1] {
+    match dijkstra(&roads(&intersections), &[(0, 0)], Some(1)).path(1) {
         None => {
             writeln!(&mut w, "impossible").unwrap();
         }
-        Some(prev) => {
-            let mut full_path = vec![1];
-            let mut prev = Some(prev);
-
-            while let Some(vertex) = prev {
-                full_path.push(vertex);
-                prev = path[vertex];
-            }
-
-            full_path.reverse();
+        Some(full_path) => {
             let full_path: Vec<String> = full_path.iter().map(|&vertex| vertex.to_string()).collect();
 
             writeln!(&mut w, "{} {}", full_path.len(), full_path.join(" ")).unwrap();
@@ -59,83 +52,10 @@ fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn dijkstra(from: usize, to: usize, intersections: &Vec<HashMap<usize, usize>>, stop_when_found: bool) -> Vec<Option<usize>> {
-
-    #[derive(Eq, PartialEq)]
-    struct Conn {
-        cost: usize,
-        vertex: usize
-    }
-
-    impl Ord for Conn {
-        fn cmp(&self, other: &Self) -> Ordering { 
-            other.cost.cmp(&self.cost).then_with(|| self.vertex.cmp(&other.vertex))
-        }
-    }
-
-    impl PartialOrd for Conn {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> { 
-            Some(self.cmp(other))
-         }
-    }
-    
-    // Dijkstra
-    let mut dist = vec![std::usize::MAX; intersections.len()];
-    dist[from] = 0;
-    let mut prev: Vec<Option<usize>> = vec![None; intersections.len()];
-
-    let mut heap: BinaryHeap<Conn> = BinaryHeap::new();
-    heap.push(Conn {cost: 0, vertex: from});
-
-    while let Some(Conn {cost, vertex}) = heap.pop() {
-        if stop_when_found && vertex == to {
-            break;
-        }
-
-        if cost > dist[vertex] {
-            continue;
-        }
-
-        for (&road_to, &road_cost) in intersections.get(vertex).unwrap().iter() {
-            if dist[vertex] + road_cost < dist[road_to] {
-                dist[road_to] = dist[vertex] + road_cost;
-                prev[road_to] = Some(vertex);
-                heap.push(Conn {
-                    cost: dist[road_to],
-                    vertex: road_to
-                });
-            }
-        }
-    }
-
-    prev
-}
-
-
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn detour_sampleinputs() {
-        for mut file in std::fs::read_dir("input/detour")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert_eq!(
-                std::str::from_utf8(&output_writer).unwrap().trim(),
-                std::fs::read_to_string(&file).unwrap().trim(),
-                "file: {:?}",
-                file
-            );
-        }
+        crate::judge::test_samples("detour", |input, output| super::solve(input, output));
     }
 }
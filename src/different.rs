@@ -1,10 +1,15 @@
-use std::io::{self, BufRead};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
 
-// Should not be seen as a 'safe' program since it will just panic whenever unexpected behaviour occurs
-pub fn main() {
-    let stdin = io::stdin();
+/// main entry point of program when invoked directly in the terminal
+#[allow(dead_code)]
+fn main() -> Result<(), Box<dyn Error>> {
+    solve(io::stdin().lock(), io::stdout().lock())
+}
 
-    for line in stdin.lock().lines() {
+// Should not be seen as a 'safe' program since it will just panic whenever unexpected behaviour occurs
+fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
+    for line in input.lines() {
         match &line.unwrap()[..] {
             "\n" => {
                 // End of input, gracefully shutdown
@@ -12,9 +17,19 @@ pub fn main() {
             },
             inp => {
                 let mut vals = inp.split(' ').map(|v| v.parse::<i64>().unwrap());
-                println!("{}",  (vals.next().unwrap() - vals.next().unwrap()).abs());
+                writeln!(&mut w, "{}",  (vals.next().unwrap() - vals.next().unwrap()).abs()).unwrap();
             } 
         }
         
     }
-}
\ No newline at end of file
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn different_sampleinputs() {
+        crate::judge::test_samples("different", |input, output| super::solve(input, output));
+    }
+}
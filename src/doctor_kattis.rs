@@ -1,7 +1,8 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::collections::BTreeSet;
-use std::io::{self, BufRead};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
 use std::rc::Rc;
 
 #[derive(Eq, PartialEq, Debug)]
@@ -23,14 +24,19 @@ impl PartialOrd for Patient {
     }
 }
 
-fn main() {
+/// main entry point of program when invoked directly in the terminal
+#[allow(dead_code)]
+fn main() -> Result<(), Box<dyn Error>> {
+    solve(io::stdin().lock(), io::stdout().lock())
+}
+
+fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
     let mut patients_map: HashMap<String, Rc<Patient>> = HashMap::new();
     let mut patients: BTreeSet<Rc<Patient>> = BTreeSet::new();
 
     let mut cur = 0;
 
-    let stdin = io::stdin();
-    for l in stdin.lock().lines().map(|l| l.unwrap()).skip(1) {
+    for l in input.lines().map(|l| l.unwrap()).skip(1) {
         let mut line = l.split(" ");
 
         match line.next().unwrap() {
@@ -62,14 +68,22 @@ fn main() {
             },
             "3" => {
                 if patients.is_empty() {
-                    println!("The clinic is empty");
+                    writeln!(&mut w, "The clinic is empty").unwrap();
                 } else {
-                    println!("{}", patients.iter().next().unwrap().name);
+                    writeln!(&mut w, "{}", patients.iter().next().unwrap().name).unwrap();
                 }
             },
             _ => panic!()
         }
     }
 
+    Ok(())
+}
 
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn doctor_kattis_sampleinputs() {
+        crate::judge::test_samples("doctor_kattis", |input, output| super::solve(input, output));
+    }
+}
@@ -1,7 +1,20 @@
 use std::error::Error;
-use std::collections::BinaryHeap;
-use std::cmp::Ordering;
 use std::io::{self, BufRead, Write};
+use std::ops::Add;
+
+use crate::snippets::graph::dijkstra;
+
+/// Water level of a cell, a path is as high as its highest cell so adding takes the maximum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Level(isize);
+
+impl Add for Level {
+    type Output = Level;
+
+    fn add(self, other: Level) -> Level {
+        Level(self.0.max(other.0))
+    }
+}
 
 /// main entry point of program when invoked directly in the terminal
 #[allow(dead_code)]
@@ -19,63 +32,22 @@ fn solve(input: impl BufRead, mut out: impl Write) -> Result<(), Box<dyn Error>>
     let last_line = lines.next().unwrap();
     let (i, j) = (last_line[0] as usize - 1, last_line[1] as usize - 1);
 
-    let mut min_reachable: Vec<Vec<isize>> = vec![vec![0; w]; h];
-    min_reachable[i][j] = map[i][j];
-
-    #[derive(Debug, PartialEq, Eq)]
-    struct Check {
-        loc: (isize, isize),
-        depth: isize
-    }
-
-    impl Ord for Check {
-        fn cmp(&self, other: &Self) -> Ordering { 
-            other.depth.cmp(&self.depth).then_with(|| self.loc.cmp(&other.loc))
-        }
-    }
-
-    impl PartialOrd for Check {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> { 
-            Some(self.cmp(other))
-         }
-    }
-
-    let mut queue: BinaryHeap<Check> = BinaryHeap::new();
-    queue.push(Check {
-        loc: (i as isize, j as isize),
-        depth: map[i][j]
-    });
-
+    // Water drains through cells below sea level, to the lowest level reachable without going up over a higher cell
     let neighbours = vec![(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (-1, 1), (1, 1), (1, -1)];
-
-    while let Some(Check {
-        loc: (x, y),
-        depth
-    }) = queue.pop() {
-        if min_reachable[x as usize][y as usize] < depth {
-            continue;
-        }
-        
-        for (dx, dy) in &neighbours {
-            if x + dx >= 0 && y + dy >= 0 && x + dx < h as isize && y + dy < w as isize &&
-                 min_reachable[x as usize][y as usize] < min_reachable[(x + dx) as usize][(y + dy) as usize]
-                 && min_reachable[(x + dx) as usize][(y + dy) as usize] > map[(x + dx) as usize][(y + dy) as usize] {
-                min_reachable[(x + dx) as usize][(y + dy) as usize] = std::cmp::max(min_reachable[x as usize][y as usize], map[(x + dx) as usize][(y + dy) as usize]);
-                queue.push(Check {
-                    loc: (x + dx, y + dy),
-                    depth: min_reachable[(x + dx) as usize][(y + dy) as usize]
-                });
-            }
-        }
-    }
-
-    let mut total = 0;
+    let mut graph: Vec<Vec<(usize, Level)>> = vec![Vec::new(); h * w];
     for x in 0..h {
         for y in 0..w {
-            total += -min_reachable[x][y];
+            for (dx, dy) in &neighbours {
+                let (nx, ny) = (x as isize + dx, y as isize + dy);
+                if nx >= 0 && ny >= 0 && nx < h as isize && ny < w as isize && map[nx as usize][ny as usize] < 0 {
+                    graph[x * w + y].push((nx as usize * w + ny as usize, Level(map[nx as usize][ny as usize])));
+                }
+            }
         }
     }
 
+    let total: isize = dijkstra(&graph, &[(i * w + j, Level(map[i][j]))], None).dist.iter().flatten().map(|level| -level.0).sum();
+
     writeln!(&mut out, "{}", total).unwrap();
     
     Ok(())
@@ -83,28 +55,8 @@ fn solve(input: impl BufRead, mut out: impl Write) -> Result<(), Box<dyn Error>>
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn emptyingbaltic_sampleinputs() {
-        for mut file in std::fs::read_dir("input/emptyingbaltic")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert_eq!(
-                std::str::from_utf8(&output_writer).unwrap().trim(),
-                std::fs::read_to_string(&file).unwrap().trim(),
-                "file: {:?}",
-                file
-            );
-        }
+        crate::judge::test_samples("emptyingbaltic", |input, output| super::solve(input, output));
     }
 }
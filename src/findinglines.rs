@@ -108,27 +108,64 @@ fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::judge::Rng;
 
     #[test]
     fn findinglines_sampleinputs() {
-        for mut file in std::fs::read_dir("input/findinglines")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert_eq!(
-                std::str::from_utf8(&output_writer).unwrap().trim(),
-                std::fs::read_to_string(&file).unwrap().trim(),
-                "file: {:?}",
-                file
-            );
+        crate::judge::test_samples("findinglines", |input, output| super::solve(input, output));
+    }
+
+    /// Counts the points on the line through every pair with an exact cross product
+    fn brute(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
+        let mut lines = input.lines().map(|x| x.unwrap());
+        let n = lines.next().unwrap().parse::<usize>()?;
+        let p = lines.next().unwrap().parse::<usize>()?;
+        let points: Vec<(i64, i64)> = (&mut lines).take(n).map(|l| {
+            let mut ps = l.split(' ');
+            (ps.next().unwrap().parse().unwrap(), ps.next().unwrap().parse().unwrap())
+        }).collect();
+
+        let expect = (n * p + 99) / 100;
+        let possible = n <= 2 || expect <= 2 || (0..n).any(|a| (a + 1..n).any(|b| {
+            let ((ax, ay), (bx, by)) = (points[a], points[b]);
+            points.iter().filter(|&&(cx, cy)| (bx - ax) * (cy - ay) == (by - ay) * (cx - ax)).count() >= expect
+        }));
+
+        writeln!(&mut w, "{}", if possible { "possible" } else { "impossible" })?;
+        Ok(())
+    }
+
+    /// Distinct points on a small grid, often with a large part of them on a single line
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let n = rng.range(1..=size + 2);
+        let range = size as i32 + 2;
+        let mut points = Vec::new();
+
+        if rng.chance(0.5) {
+            let (x, y) = (rng.range(-range..=range), rng.range(-range..=range));
+            let (dx, dy) = *rng.choose(&[(1, 0), (0, 1), (1, 1), (1, -1), (2, 1), (1, 3)]);
+            for k in 0..rng.range(0..=n) as i32 {
+                points.push((x + k * dx, y + k * dy));
+            }
+        }
+        while points.len() < n {
+            let point = (rng.range(-range..=range), rng.range(-range..=range));
+            if !points.contains(&point) {
+                points.push(point);
+            }
+        }
+        rng.shuffle(&mut points);
+
+        let mut input = format!("{}\n{}\n", n, rng.range(20..=100));
+        for (x, y) in points {
+            input.push_str(&format!("{} {}\n", x, y));
         }
+        input
+    }
+
+    #[test]
+    #[ignore = "the solution is randomized, a rare failure is expected"]
+    fn findinglines_stress() {
+        crate::judge::stress("findinglines", generate, |input, output| solve(input, output), |input, output| brute(input, output));
     }
 }
@@ -1,21 +1,15 @@
-use std::{cmp::Ordering, collections::{BinaryHeap, HashMap}, io::{self, BufRead}};
+use std::{error::Error, io::{self, BufRead, Write}};
 
-#[derive(Debug, Clone)]
-struct Intersection {
-    i: u64,
-    distance_to_firestation: u64,
-    roads: Vec<(u64, u64)> // Intersection / length
-}
+use crate::snippets::graph::dijkstra;
 
-impl Default for Intersection {
-    fn default() -> Self {
-        Self { i: Default::default(), distance_to_firestation: std::u64::MAX, roads: Default::default() }
-    }
+/// main entry point of program when invoked directly in the terminal
+#[allow(dead_code)]
+fn main() -> Result<(), Box<dyn Error>> {
+    solve(io::stdin().lock(), io::stdout().lock())
 }
 
-pub fn main() {
-    let stdin = io::stdin();
-    let mut lines = stdin.lock().lines();
+fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
+    let mut lines = input.lines();
 
     let test_cases: u8 = lines.next().unwrap().unwrap().parse().unwrap();
 
@@ -28,14 +22,12 @@ pub fn main() {
         let mut l1 = line.split(' ');
 
         let n_fire_stations: u64 = l1.next().unwrap().parse().unwrap();
-        let n_intersections: u64 = l1.next().unwrap().parse().unwrap();
+        let n_intersections: usize = l1.next().unwrap().parse().unwrap();
 
-        let mut intersections: HashMap<u64, Intersection> = (1..=n_intersections).map(|i| (i, Intersection {
-            i,
-            ..Default::default()
-        })).collect();
+        // Intersections are 1-based, roads: intersection / length
+        let mut intersections: Vec<Vec<(usize, u64)>> = vec![vec![]; n_intersections + 1];
 
-        let mut firestations: Vec<u64> = vec![];
+        let mut firestations: Vec<usize> = vec![];
 
         for _ in 0..n_fire_stations {
             firestations.push(lines.next().unwrap().unwrap().parse().unwrap());
@@ -47,27 +39,25 @@ pub fn main() {
             }
 
             let mut splitted = line.split(' ');
-            let from: u64 = splitted.next().unwrap().parse().unwrap();
-            let to: u64 = splitted.next().unwrap().parse().unwrap();
+            let from: usize = splitted.next().unwrap().parse().unwrap();
+            let to: usize = splitted.next().unwrap().parse().unwrap();
             let length: u64 = splitted.next().unwrap().parse().unwrap();
 
-            intersections.get_mut(&from).unwrap().roads.push((to, length));
-            intersections.get_mut(&to).unwrap().roads.push((from, length));
+            intersections[from].push((to, length));
+            intersections[to].push((from, length));
         }
 
-        for firestation in firestations {
-            if firestation <= n_intersections {
-                relax_firestation(firestation, &mut intersections);
-            }
-        }
+        let mut sources: Vec<(usize, u64)> =
+            firestations.into_iter().filter(|&firestation| firestation <= n_intersections).map(|firestation| (firestation, 0)).collect();
 
         let mut min_distance = std::u64::MAX;
         let mut min_firestation = 0;
 
         for i in 1..=n_intersections {
-            let mut intersections_clone = intersections.clone();
-            relax_firestation(i, &mut intersections_clone);
-            let distance = max_distance(&intersections_clone);
+            sources.push((i, 0));
+            let distances = dijkstra(&intersections, &sources, None).dist;
+            sources.pop();
+            let distance = distances[1..].iter().map(|d| d.unwrap_or(u64::MAX)).max().unwrap();
 
             if distance < min_distance || (distance == min_distance && i < min_firestation) {
                 min_distance = distance;
@@ -75,55 +65,16 @@ pub fn main() {
             }
         }
 
-        println!("{}\n", min_firestation);
+        writeln!(&mut w, "{}\n", min_firestation).unwrap();
     }
-}
 
-fn max_distance(intersections: &HashMap<u64, Intersection>) -> u64 {
-    intersections.values().map(|int| int.distance_to_firestation).max().unwrap()
+    Ok(())
 }
 
-fn relax_firestation(firestation: u64, intersections: &mut HashMap<u64, Intersection>) {
-    #[derive(Debug, PartialEq, Eq)]
-    struct FoundCost {
-        cost: u64,
-        intersection: u64
-    }
-
-    impl Ord for FoundCost {
-        fn cmp(&self, other: &Self) -> Ordering {
-            other.cost.cmp(&self.cost).then_with(|| other.intersection.cmp(&self.intersection))
-        }
-    }
-
-    impl PartialOrd for FoundCost {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-            Some(self.cmp(other))
-        }
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn firestation_sampleinputs() {
+        crate::judge::test_samples("firestation", |input, output| super::solve(input, output));
     }
-
-    let mut heap: BinaryHeap<FoundCost> = BinaryHeap::new();
-    heap.push(FoundCost { cost: 0, intersection: firestation });
-
-    while let Some(FoundCost { cost, intersection }) = heap.pop() {
-        
-        let inter = intersections.get(&intersection).unwrap();
-
-        // Cannot be relaxed since distance to firestation is already lower
-        if cost > inter.distance_to_firestation { continue; }
-
-        for (to_intersection, distance) in &inter.roads {
-            let to_inter = intersections.get(to_intersection).unwrap();
-            let next_cost = cost + distance;
-
-            if next_cost < to_inter.distance_to_firestation {
-                heap.push(FoundCost {
-                    cost: next_cost,
-                    intersection: *to_intersection
-                });
-            }
-        }
-
-        intersections.get_mut(&intersection).unwrap().distance_to_firestation = cost;
-    }
-}
\ No newline at end of file
+}
@@ -111,28 +111,8 @@ fn dfs(node: usize, nodes: &Vec<Node>, visited: &mut Vec<bool>, color: &mut Vec<
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn hoppers_sampleinputs() {
-        for mut file in std::fs::read_dir("input/hoppers")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert_eq!(
-                std::str::from_utf8(&output_writer).unwrap().trim(),
-                std::fs::read_to_string(&file).unwrap().trim(),
-                "file: {:?}",
-                file
-            );
-        }
+        crate::judge::test_samples("hoppers", |input, output| super::solve(input, output));
     }
 }
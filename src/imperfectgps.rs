@@ -74,25 +74,8 @@ fn path_len(path: &Vec<(f64, f64, usize)>) -> f64 {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn imperfectgps_sampleinputs() {
-        for mut file in std::fs::read_dir("input/imperfectgps")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert!(
-                (std::str::from_utf8(&output_writer).unwrap().trim().parse::<f64>().unwrap() - std::fs::read_to_string(&file).unwrap().trim().parse::<f64>().unwrap()).abs() < 10.0f64.powi(-5)
-            );
-        }
+        crate::judge::test_samples("imperfectgps", |input, output| super::solve(input, output));
     }
 }
@@ -61,26 +61,8 @@ fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn islandhopping_sampleinputs() {
-        for mut file in std::fs::read_dir("input/islandhopping")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            let mut output = std::str::from_utf8(&output_writer).unwrap().trim().lines().map(|x| x.parse::<f32>().unwrap());
-            for answer in std::fs::read_to_string(&file).unwrap().trim().lines().map(|x| x.parse::<f32>().unwrap()) {
-                assert!((answer - output.next().unwrap()).abs() < 10.0f32.powi(-4), "file: {:?}", file);
-            }
-        }
+        crate::judge::test_samples("islandhopping", |input, output| super::solve(input, output));
     }
 }
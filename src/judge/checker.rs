@@ -0,0 +1,197 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::diff;
+
+/// Decides whether the output of a solution is accepted, mirroring the output validators Kattis uses
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum Checker {
+    /// Every line must match, apart from trailing whitespace and leading or trailing empty lines
+    #[default]
+    Exact,
+    /// Every whitespace separated token must match, the amount and kind of whitespace is ignored
+    Tokens,
+    /// Tokens must match, but numbers are accepted if they are within the absolute or relative error
+    Float { abs: f64, rel: f64 },
+    /// Tokens must match when compared case-insensitively
+    CaseInsensitive,
+}
+
+impl Checker {
+    /// Float checker accepting an absolute or relative error of at most `tolerance`
+    pub fn float(tolerance: f64) -> Self {
+        Checker::Float { abs: tolerance, rel: tolerance }
+    }
+
+    /// Returns a description of the first difference if the output is not accepted: its position, the amount of
+    /// differing lines and the lines around it, and for the float checker the error of the first differing number
+    pub fn check(&self, output: &str, answer: &str) -> Result<(), String> {
+        match self {
+            Checker::Exact => check_lines(output.trim(), answer.trim()),
+            Checker::Tokens => compare_tokens(output, answer, |o, a| o == a, |_, _| None),
+            Checker::CaseInsensitive => compare_tokens(output, answer, |o, a| o.eq_ignore_ascii_case(a), |_, _| None),
+            Checker::Float { abs, rel } => {
+                let numbers = |o: &str, a: &str| Some((o.parse::<f64>().ok()?, a.parse::<f64>().ok()?));
+                compare_tokens(
+                    output,
+                    answer,
+                    |o, a| match numbers(o, a) {
+                        Some((o, a)) => (o - a).abs() <= *abs || (o - a).abs() <= rel * a.abs(),
+                        None => o == a,
+                    },
+                    |o, a| {
+                        let (o, a) = numbers(o, a)?;
+                        Some(format!(
+                            "absolute error {:e}, relative error {:e}, accepted are {:e} and {:e}",
+                            (o - a).abs(),
+                            (o - a).abs() / a.abs(),
+                            abs,
+                            rel
+                        ))
+                    },
+                )
+            }
+        }
+    }
+}
+
+/// Every line must match apart from trailing whitespace
+fn check_lines(output: &str, answer: &str) -> Result<(), String> {
+    let output_lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+    let answer_lines: Vec<&str> = answer.lines().map(str::trim_end).collect();
+
+    for line in 1..=output_lines.len().max(answer_lines.len()) {
+        let (o, a) = (output_lines.get(line - 1), answer_lines.get(line - 1));
+        if o == a {
+            continue;
+        }
+
+        let line_tokens = |text| diff::tokens(text).into_iter().filter(|t| t.line == line).collect::<Vec<_>>();
+        let (output_tokens, answer_tokens) = (line_tokens(output), line_tokens(answer));
+        let first = (0..output_tokens.len().max(answer_tokens.len()))
+            .find(|&i| output_tokens.get(i).map(|t| t.text) != answer_tokens.get(i).map(|t| t.text));
+
+        let headline = match first {
+            Some(i) => diff::mismatch(answer_tokens.get(i), output_tokens.get(i)),
+            None if o.is_some() && a.is_some() => format!("line {}: only the whitespace differs from the answer", line),
+            // An empty line against the end of the output
+            None => format!("line {}: expected {}, got {}", line, a.map_or("the end of the output", |_| "an empty line"), o.map_or("the end of the output", |_| "an empty line")),
+        };
+        return Err(format!("{}\n{}", headline, diff::context(output, answer, line, |o, a| o.trim_end() == a.trim_end())));
+    }
+    Ok(())
+}
+
+/// Compares the whitespace separated tokens with `eq`, `note` can explain why two tokens are not equal
+fn compare_tokens(
+    output: &str,
+    answer: &str,
+    eq: impl Fn(&str, &str) -> bool,
+    note: impl Fn(&str, &str) -> Option<String>,
+) -> Result<(), String> {
+    let output_tokens = diff::tokens(output);
+    let answer_tokens = diff::tokens(answer);
+
+    for i in 0..output_tokens.len().max(answer_tokens.len()) {
+        let (o, a) = (output_tokens.get(i), answer_tokens.get(i));
+        if let (Some(o), Some(a)) = (o, a) {
+            if eq(o.text, a.text) {
+                continue;
+            }
+        }
+
+        let mut res = diff::mismatch(a, o);
+        if let Some(note) = o.zip(a).and_then(|(o, a)| note(o.text, a.text)) {
+            res = format!("{}\n{}", res, note);
+        }
+
+        let line = a.or(o).unwrap().line;
+        let same_line = |o: &str, a: &str| {
+            let (o, a) = (diff::tokens(o), diff::tokens(a));
+            o.len() == a.len() && o.iter().zip(&a).all(|(o, a)| eq(o.text, a.text))
+        };
+        return Err(format!("{}\n{}", res, diff::context(output, answer, line, same_line)));
+    }
+    Ok(())
+}
+
+/// Parses `exact`, `tokens`, `case-insensitive`, `float`, `float:<tolerance>` and `float:<abs>:<rel>`
+impl FromStr for Checker {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split(':');
+        let checker = match parts.next().unwrap() {
+            "exact" => Checker::Exact,
+            "tokens" => Checker::Tokens,
+            "case-insensitive" => Checker::CaseInsensitive,
+            "float" => {
+                let mut tolerance = || -> Result<Option<f64>, String> {
+                    parts.next().map(|t| t.parse().map_err(|_| format!("invalid tolerance '{}'", t))).transpose()
+                };
+                let abs = tolerance()?.unwrap_or(1e-6);
+                match tolerance()? {
+                    Some(rel) => Checker::Float { abs, rel },
+                    None => Checker::float(abs),
+                }
+            }
+            other => return Err(format!("unknown checker '{}', expected exact, tokens, float or case-insensitive", other)),
+        };
+
+        if parts.next().is_some() {
+            return Err(format!("too many arguments for checker '{}'", s));
+        }
+
+        Ok(checker)
+    }
+}
+
+impl fmt::Display for Checker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Checker::Exact => write!(f, "exact"),
+            Checker::Tokens => write!(f, "tokens"),
+            Checker::CaseInsensitive => write!(f, "case-insensitive"),
+            Checker::Float { abs, rel } => write!(f, "float:{}:{}", abs, rel),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkers() {
+        assert!(Checker::Exact.check("1 2\r\n3  \n\n", "1 2\n3").is_ok());
+        assert!(Checker::Exact.check("1  2\n3", "1 2\n3").is_err());
+        assert!(Checker::Tokens.check("1  2\n3", "1 2 3\n").is_ok());
+        assert!(Checker::Tokens.check("1 2", "1 2 3").is_err());
+        assert!(Checker::CaseInsensitive.check("YES\nno", "yes No").is_ok());
+        assert!(Checker::float(1e-6).check("2.0000001 impossible", "2.000 impossible").is_ok());
+        assert!(Checker::float(1e-6).check("1000000.5", "1000000").is_ok());
+        assert!(Checker::float(1e-6).check("2.00001", "2.000").is_err());
+        assert_eq!("float:1e-4".parse(), Ok(Checker::float(1e-4)));
+        assert_eq!("float:0:1e-9".parse(), Ok(Checker::Float { abs: 0.0, rel: 1e-9 }));
+        assert!("float:x".parse::<Checker>().is_err());
+    }
+
+    #[test]
+    fn differences() {
+        let answer: String = (1..=20).map(|i| format!("{} {}\n", i, i * i)).collect();
+        let output = answer.replace("7 49", "7 48").replace("15 225", "15 224");
+        let message = Checker::Exact.check(&output, &answer).unwrap_err();
+        let lines: Vec<&str> = message.lines().collect();
+        assert_eq!(lines[..2], ["line 7, column 3: expected '49', got '48'", "2 of 20 lines differ"]);
+        assert_eq!(lines.len(), 8);
+        assert!(lines[5].starts_with(">  7 7 49 ") && lines[5].ends_with("| 7 48"), "{}", message);
+        assert!(lines[4].starts_with("   6 6 36 ") && lines[7].starts_with("   9 9 81 "), "{}", message);
+
+        assert!(Checker::Exact.check("1  2", "1 2").unwrap_err().starts_with("line 1: only the whitespace differs"));
+        assert!(Checker::Exact.check("1\n2", "1").unwrap_err().starts_with("line 2, column 1: expected the end of the output, got '2'"));
+        assert!(Checker::Tokens.check("1\n2", "1 3").unwrap_err().starts_with("expected '3' at line 1, column 3, got '2' at line 2, column 1"));
+
+        let message = Checker::float(1e-6).check("1.5 2.5", "1.5 2").unwrap_err();
+        assert!(message.starts_with("line 1, column 5: expected '2', got '2.5'\nabsolute error 5e-1, relative error 2.5e-1, accepted are 1e-6 and 1e-6\n1 of 1 lines differ"));
+    }
+}
@@ -0,0 +1,121 @@
+//! Describes where an output differs from the answer, so a wrong answer on a large output stays readable: the first
+//! differing token with its position, the amount of differing lines and the lines around it side by side.
+
+/// Lines shown before and after the first differing line
+const CONTEXT: usize = 2;
+/// Characters shown of every line, longer lines are cut off
+const WIDTH: usize = 36;
+
+/// A whitespace separated token, `line` and `column` are 1-based
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Token<'a> {
+    pub text: &'a str,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub(super) fn tokens(text: &str) -> Vec<Token<'_>> {
+    let mut res = Vec::new();
+    for (line, l) in text.lines().enumerate() {
+        let mut start = None;
+        for (column, (i, c)) in l.char_indices().chain(std::iter::once((l.len(), ' '))).enumerate() {
+            match (start, c.is_whitespace()) {
+                (None, false) => start = Some((i, column)),
+                (Some((s, column)), true) => {
+                    res.push(Token { text: &l[s..i], line: line + 1, column: column + 1 });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+    res
+}
+
+/// Describes the first differing token, `None` is the end of the output
+pub(super) fn mismatch(expected: Option<&Token>, got: Option<&Token>) -> String {
+    match (expected, got) {
+        (Some(e), Some(g)) if (e.line, e.column) == (g.line, g.column) => {
+            format!("line {}, column {}: expected '{}', got '{}'", e.line, e.column, e.text, g.text)
+        }
+        (Some(e), Some(g)) => format!(
+            "expected '{}' at line {}, column {}, got '{}' at line {}, column {}",
+            e.text, e.line, e.column, g.text, g.line, g.column
+        ),
+        (Some(e), None) => format!("line {}, column {}: expected '{}', got the end of the output", e.line, e.column, e.text),
+        (None, Some(g)) => format!("line {}, column {}: expected the end of the output, got '{}'", g.line, g.column, g.text),
+        (None, None) => "the output differs from the answer".to_string(),
+    }
+}
+
+fn cut(line: &str) -> String {
+    if line.chars().count() > WIDTH {
+        format!("{}…", line.chars().take(WIDTH - 1).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+/// Counts the differing lines and shows the lines around `line` of the expected and the actual output side by side,
+/// differing lines are marked with a `>`
+pub(super) fn context(output: &str, answer: &str, line: usize, same: impl Fn(&str, &str) -> bool) -> String {
+    let output: Vec<&str> = output.lines().collect();
+    let answer: Vec<&str> = answer.lines().collect();
+    let len = output.len().max(answer.len());
+    let differs = |i: usize| match (output.get(i), answer.get(i)) {
+        (Some(o), Some(a)) => !same(o, a),
+        _ => true,
+    };
+
+    let count = (0..len).filter(|&i| differs(i)).count();
+    let mut res = format!("{} of {} lines differ\n", count, len);
+
+    let number_width = len.to_string().len();
+    res.push_str(&format!("  {:>n$} {:<w$} | {}\n", "", "expected", "got", n = number_width, w = WIDTH));
+    for i in line.saturating_sub(CONTEXT + 1)..(line + CONTEXT).min(len) {
+        let show = |lines: &[&str]| lines.get(i).map_or("<end of output>".to_string(), |l| cut(l));
+        res.push_str(&format!(
+            "{} {:>n$} {:<w$} | {}\n",
+            if differs(i) { '>' } else { ' ' },
+            i + 1,
+            show(&answer),
+            show(&output),
+            n = number_width,
+            w = WIDTH
+        ));
+    }
+    res.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions() {
+        let tokens = tokens("1  22\n\n é 4\n");
+        let token = |text, line, column| Token { text, line, column };
+        assert_eq!(tokens, vec![token("1", 1, 1), token("22", 1, 4), token("é", 3, 2), token("4", 3, 4)]);
+
+        assert_eq!(mismatch(Some(&tokens[0]), Some(&token("2", 1, 1))), "line 1, column 1: expected '1', got '2'");
+        assert_eq!(mismatch(Some(&tokens[1]), None), "line 1, column 4: expected '22', got the end of the output");
+        assert_eq!(
+            mismatch(Some(&tokens[1]), Some(&token("3", 2, 1))),
+            "expected '22' at line 1, column 4, got '3' at line 2, column 1"
+        );
+    }
+
+    #[test]
+    fn side_by_side() {
+        let answer = "1\n2\n3\n4\n5\n6\n7\n".to_string() + &"x".repeat(40);
+        let output = "1\n2\n3\n4\n0\n6\n7\n";
+        assert_eq!(
+            context(output, &answer, 5, |o, a| o == a),
+            format!(
+                "2 of 8 lines differ\n    {:<36} | got\n  3 {:<36} | 3\n  4 {:<36} | 4\n> 5 {:<36} | 0\n  6 {:<36} | 6\n  7 {:<36} | 7",
+                "expected", "3", "4", "5", "6", "7"
+            )
+        );
+        assert!(context(output, &answer, 8, |o, a| o == a).ends_with(&format!("> 8 {}… | <end of output>", "x".repeat(35))));
+    }
+}
@@ -0,0 +1,106 @@
+//! Random inputs for stress tests and `kattis gen`, problems register a generator in their tests with [`generate`].
+use std::fs;
+use std::ops::RangeInclusive;
+
+use crate::problem::Problem;
+
+/// Integer types a random number can be drawn from
+pub(crate) trait Int: Copy {
+    fn to_i128(self) -> i128;
+    fn from_i128(n: i128) -> Self;
+}
+
+macro_rules! int {
+    ($($t:ty),*) => {
+        $(impl Int for $t {
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+
+            fn from_i128(n: i128) -> Self {
+                n as $t
+            }
+        })*
+    };
+}
+
+int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Small and fast pseudo random number generator (splitmix64), the same seed always gives the same numbers
+#[derive(Debug, Clone)]
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniformly chosen number in the inclusive range
+    pub fn range<T: Int>(&mut self, range: RangeInclusive<T>) -> T {
+        let (low, high) = (range.start().to_i128(), range.end().to_i128());
+        assert!(low <= high, "empty range");
+        T::from_i128(low + self.next_u64() as i128 % (high - low + 1))
+    }
+
+    /// True with probability `p`
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i));
+        }
+    }
+}
+
+/// Creates random test input for a problem, larger sizes should give larger inputs
+pub(crate) trait Generator {
+    fn generate(&mut self, rng: &mut Rng, size: usize) -> String;
+}
+
+impl<F: FnMut(&mut Rng, usize) -> String> Generator for F {
+    fn generate(&mut self, rng: &mut Rng, size: usize) -> String {
+        self(rng, size)
+    }
+}
+
+/// Registers the generator of a problem. When run by `kattis gen`, which sets `KATTIS_GEN_SIZE` (a number or `max`)
+/// and `KATTIS_GEN_SEED`, a single input is written to `input/<problem>/gen/<size>-<seed>.in`. In a normal test run
+/// a few small inputs are generated to check the generator itself.
+pub(crate) fn generate<G: Generator>(problem: &str, max_size: usize, mut generator: G) {
+    let (size, seed) = match (std::env::var("KATTIS_GEN_SIZE"), std::env::var("KATTIS_GEN_SEED")) {
+        (Ok(size), Ok(seed)) => (size, seed.parse::<u64>().expect("invalid KATTIS_GEN_SEED")),
+        _ => {
+            for seed in 0..10 {
+                let input = generator.generate(&mut Rng::new(seed), 1 + seed as usize % max_size);
+                assert!(!input.trim().is_empty(), "{} generated an empty input with seed {}", problem, seed);
+            }
+            return;
+        }
+    };
+
+    let size = match size.as_str() {
+        "max" => max_size,
+        size => size.parse().expect("invalid KATTIS_GEN_SIZE"),
+    };
+    assert!(size <= max_size, "the maximum size of {} is {}", problem, max_size);
+
+    let input = generator.generate(&mut Rng::new(seed), size);
+    let path = Problem::new(problem).unwrap().generated_dir().join(format!("{}-{}.in", size, seed));
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, &input).unwrap();
+    println!("wrote {} bytes to {}", input.len(), path.display());
+}
@@ -0,0 +1,232 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::runner::{apply_limits, signal_name, wall_limit, Limits, SIGKILL, SIGXCPU};
+use super::Verdict;
+
+/// How often the judge checks whether the solution is still alive while waiting for it
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// When the judge waits for output and the solution did not use any CPU time for a quarter of the time limit, but at
+/// least this long, the solution is waiting for input as well and nothing will ever happen again. The window is wide
+/// so a solution which is merely not scheduled on a loaded machine is not mistaken for a deadlock.
+const MIN_IDLE_LIMIT: Duration = Duration::from_secs(2);
+
+/// The other side of an interactive problem, playing the role of the Kattis interactor
+pub(crate) trait Judge {
+    /// Talks with the solution through `io`, returns `Ok(())` if the solution did everything right.
+    /// Errors of the channel (time limit, deadlock, crashed solution) can be forwarded with `?`.
+    fn interact(&mut self, io: &mut Channel) -> Result<(), Verdict>;
+}
+
+/// Line based connection between the judge and the solution running in another process
+pub(crate) struct Channel {
+    child: Child,
+    input: Option<Sender<String>>,
+    output: Receiver<String>,
+    started: Instant,
+    /// CPU time limit, enforced by `setrlimit`
+    time_limit: Duration,
+    wall_limit: Duration,
+    transcript: Vec<String>,
+}
+
+impl Channel {
+    /// Sends a single line to the solution
+    pub fn write_line(&mut self, line: impl fmt::Display) {
+        let line = line.to_string();
+        self.transcript.push(format!("> {}", line));
+        if let Some(input) = &self.input {
+            // The solution might have exited already, which will be noticed when reading
+            let _ = input.send(line);
+        }
+    }
+
+    /// Closes the input of the solution, any following writes are ignored
+    pub fn close(&mut self) {
+        self.input = None;
+    }
+
+    /// Waits for the next line of the solution. Fails when the solution exits, exceeds the time limit or when both
+    /// the judge and the solution are waiting for each other.
+    pub fn read_line(&mut self) -> Result<String, Verdict> {
+        let pid = self.child.id();
+        let mut cpu = cpu_time(pid);
+        let mut idle_since = Instant::now();
+        let idle_limit = (self.time_limit / 4).max(MIN_IDLE_LIMIT);
+
+        loop {
+            let remaining = self.wall_limit.saturating_sub(self.started.elapsed());
+            if remaining == Duration::from_secs(0) {
+                return Err(Verdict::TimeLimitExceeded(format!("no answer within {:.2}s wall time", self.wall_limit.as_secs_f64())));
+            }
+
+            match self.output.recv_timeout(remaining.min(POLL_INTERVAL)) {
+                Ok(line) => {
+                    self.transcript.push(format!("< {}", line));
+                    return Ok(line);
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(match self.child.wait() {
+                        Ok(status) if !status.success() => failure(status),
+                        _ => Verdict::WrongAnswer("solution exited while the judge expected more output".to_string()),
+                    });
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    let now = cpu_time(pid);
+                    if now.is_none() || now != cpu {
+                        cpu = now;
+                        idle_since = Instant::now();
+                    } else if idle_since.elapsed() >= idle_limit {
+                        return Err(Verdict::TimeLimitExceeded(
+                            "deadlock, the solution and the judge are both waiting for input (missing flush?)".to_string(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// The last lines of the conversation, `>` is written by the judge and `<` by the solution
+    pub fn transcript(&self, lines: usize) -> String {
+        self.transcript[self.transcript.len().saturating_sub(lines)..].join("\n")
+    }
+
+    /// Waits for the solution to exit after the judge is done, the judge verdict is only kept if the solution
+    /// exits cleanly without writing anything else.
+    fn finish(&mut self, verdict: Result<(), Verdict>) -> Verdict {
+        self.close();
+
+        if let Err(verdict) = verdict {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+            return verdict;
+        }
+
+        loop {
+            match self.child.try_wait() {
+                Ok(Some(status)) if !status.success() => return failure(status),
+                Ok(Some(_)) => break,
+                Ok(None) if self.started.elapsed() >= self.wall_limit => {
+                    let _ = self.child.kill();
+                    let _ = self.child.wait();
+                    return Verdict::TimeLimitExceeded("solution did not exit after the judge was done".to_string());
+                }
+                Ok(None) => thread::sleep(POLL_INTERVAL),
+                Err(e) => return Verdict::RunTimeError(e.to_string()),
+            }
+        }
+
+        match self.output.recv() {
+            Ok(line) => Verdict::WrongAnswer(format!("trailing output after the judge was done: '{}'", line)),
+            Err(_) => Verdict::Accepted,
+        }
+    }
+}
+
+/// Spawns the solution and lets the judge talk to it until the judge decides on a verdict.
+/// The transcript of the conversation is returned as well to make failures easier to debug.
+pub(crate) fn interact(mut command: Command, judge: &mut impl Judge, limits: &Limits) -> io::Result<(Verdict, String)> {
+    apply_limits(&mut command, limits)?;
+    let mut child = command.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::inherit()).spawn()?;
+
+    let (input, input_receiver) = mpsc::channel::<String>();
+    let mut stdin = child.stdin.take().unwrap();
+    thread::spawn(move || {
+        for line in input_receiver {
+            if writeln!(stdin, "{}", line).and_then(|_| stdin.flush()).is_err() {
+                break;
+            }
+        }
+    });
+
+    let (output_sender, output) = mpsc::channel::<String>();
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+    thread::spawn(move || {
+        for line in stdout.lines().map_while(Result::ok) {
+            if output_sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut channel = Channel {
+        child,
+        input: Some(input),
+        output,
+        started: Instant::now(),
+        time_limit: limits.time,
+        wall_limit: wall_limit(limits),
+        transcript: Vec::new(),
+    };
+
+    let verdict = judge.interact(&mut channel);
+    let verdict = channel.finish(verdict);
+    Ok((verdict, channel.transcript(20)))
+}
+
+/// Solutions killed by the CPU time limit exceeded it, any other failure is a run time error
+fn failure(status: ExitStatus) -> Verdict {
+    match status.signal() {
+        Some(signal @ (SIGXCPU | SIGKILL)) => Verdict::TimeLimitExceeded(format!("killed by {}", signal_name(signal))),
+        _ => Verdict::RunTimeError(status.to_string()),
+    }
+}
+
+/// User and system CPU time of a process in clock ticks, only available on Linux
+fn cpu_time(pid: u32) -> Option<u64> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // The name of the process is between parentheses and can contain spaces, so skip past it first
+    let mut fields = stat[stat.rfind(')')? + 2..].split(' ');
+    let utime: u64 = fields.nth(11)?.parse().ok()?;
+    let stime: u64 = fields.next()?.parse().ok()?;
+    Some(utime + stime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Expects every line to be echoed back, which `cat` happily does
+    struct Echo(bool);
+
+    impl Judge for Echo {
+        fn interact(&mut self, io: &mut Channel) -> Result<(), Verdict> {
+            for i in 0..3 {
+                if self.0 {
+                    io.write_line(i);
+                }
+                if io.read_line()? != i.to_string() {
+                    return Err(Verdict::WrongAnswer(format!("expected {}", i)));
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn interact_with_cat() {
+        let limits = Limits { time: Duration::from_secs(5), ..Limits::default() };
+        let (verdict, transcript) = interact(Command::new("cat"), &mut Echo(true), &limits).unwrap();
+        assert_eq!(verdict, Verdict::Accepted);
+        assert_eq!(transcript, "> 0\n< 0\n> 1\n< 1\n> 2\n< 2");
+
+        let (verdict, _) = interact(Command::new("cat"), &mut Echo(false), &limits).unwrap();
+        assert!(matches!(verdict, Verdict::TimeLimitExceeded(e) if e.starts_with("deadlock")));
+    }
+
+    #[test]
+    fn cpu_limit() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "while :; do :; done"]);
+        let limits = Limits { time: Duration::from_millis(200), ..Limits::default() };
+        let (verdict, _) = interact(command, &mut Echo(false), &limits).unwrap();
+        assert_eq!(verdict, Verdict::TimeLimitExceeded("killed by SIGXCPU".to_string()));
+    }
+}
@@ -0,0 +1,212 @@
+//! Local version of the judge of the interactive `amazing` problem.
+//!
+//! Mazes are stored as `input/amazing/*.maze` files, a grid where `#` is a wall, `.` is open space, `S` is the start
+//! and `E` is the exit. Everything outside of the grid is a wall. Lines starting with `;` are comments and an optional
+//! `limit <n>` line overrides the maximum amount of moves.
+use std::collections::VecDeque;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+use super::{Channel, Judge, Verdict};
+
+/// Maximum amount of moves a solution can make before the judge answers `wrong`
+const DEFAULT_MOVE_LIMIT: usize = 100_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Wall,
+    Open,
+    Exit,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Maze {
+    grid: Vec<Vec<Cell>>,
+    start: (usize, usize),
+    limit: usize,
+}
+
+impl Maze {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let maze = fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+        Maze::parse(&maze).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(maze: &str) -> Result<Self, String> {
+        let mut grid = Vec::new();
+        let mut start = None;
+        let mut limit = DEFAULT_MOVE_LIMIT;
+
+        for line in maze.lines().map(str::trim_end).filter(|l| !l.is_empty() && !l.starts_with(';')) {
+            if let Some(n) = line.strip_prefix("limit ") {
+                limit = n.trim().parse().map_err(|_| format!("invalid move limit '{}'", n))?;
+                continue;
+            }
+
+            let mut row = Vec::new();
+            for (x, c) in line.chars().enumerate() {
+                row.push(match c {
+                    '#' => Cell::Wall,
+                    '.' => Cell::Open,
+                    'E' => Cell::Exit,
+                    'S' if start.is_none() => {
+                        start = Some((grid.len(), x));
+                        Cell::Open
+                    }
+                    'S' => return Err("the maze has more than one start".to_string()),
+                    c => return Err(format!("unexpected character '{}' in the maze", c)),
+                });
+            }
+            grid.push(row);
+        }
+
+        match start {
+            Some(start) => Ok(Maze { grid, start, limit }),
+            None => Err("the maze has no start".to_string()),
+        }
+    }
+
+    fn cell(&self, (y, x): (usize, usize)) -> Cell {
+        self.grid.get(y).and_then(|row| row.get(x)).copied().unwrap_or(Cell::Wall)
+    }
+
+    /// The cell next to `pos` in the given direction, `None` when it lies outside of the grid
+    fn step(&self, (y, x): (usize, usize), dir: &str) -> Option<(usize, usize)> {
+        match dir {
+            "up" => Some((y.checked_sub(1)?, x)),
+            "down" => Some((y + 1, x)),
+            "left" => Some((y, x.checked_sub(1)?)),
+            "right" => Some((y, x + 1)),
+            _ => None,
+        }
+    }
+
+    /// Whether the exit can be reached from the start
+    pub fn solvable(&self) -> bool {
+        let mut seen = vec![vec![false; self.grid.iter().map(Vec::len).max().unwrap_or(0)]; self.grid.len()];
+        let mut queue = VecDeque::new();
+        seen[self.start.0][self.start.1] = true;
+        queue.push_back(self.start);
+
+        while let Some(pos) = queue.pop_front() {
+            if self.cell(pos) == Cell::Exit {
+                return true;
+            }
+
+            for dir in &["up", "down", "left", "right"] {
+                if let Some(next) = self.step(pos, dir) {
+                    if self.cell(next) != Cell::Wall && !seen[next.0][next.1] {
+                        seen[next.0][next.1] = true;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        false
+    }
+}
+
+/// Plays a maze against the solution, answering moves the same way the Kattis judge does
+pub(crate) struct MazeJudge {
+    maze: Maze,
+    pos: (usize, usize),
+    moves: usize,
+}
+
+impl MazeJudge {
+    pub fn new(maze: Maze) -> Self {
+        MazeJudge { pos: maze.start, maze, moves: 0 }
+    }
+
+    /// Answer of the judge to a single line of the solution, `Err` means the judge answers `wrong`
+    fn respond(&mut self, line: &str) -> Result<&'static str, String> {
+        if line == "no way out" {
+            return if self.maze.solvable() {
+                Err("answered 'no way out' while the exit is reachable".to_string())
+            } else {
+                Ok("solved")
+            };
+        }
+
+        if !["up", "down", "left", "right"].contains(&line) {
+            return Err(format!("invalid move '{}'", line));
+        }
+
+        self.moves += 1;
+        if self.moves > self.maze.limit {
+            return Err(format!("exceeded the limit of {} moves", self.maze.limit));
+        }
+
+        match self.maze.step(self.pos, line).map(|next| (next, self.maze.cell(next))) {
+            None | Some((_, Cell::Wall)) => Ok("wall"),
+            Some((_, Cell::Exit)) => Ok("solved"),
+            Some((next, Cell::Open)) => {
+                self.pos = next;
+                Ok("ok")
+            }
+        }
+    }
+}
+
+impl Judge for MazeJudge {
+    fn interact(&mut self, io: &mut Channel) -> Result<(), Verdict> {
+        loop {
+            let line = io.read_line()?;
+            match self.respond(&line) {
+                Ok("solved") => {
+                    io.write_line("solved");
+                    return Ok(());
+                }
+                Ok(answer) => io.write_line(answer),
+                Err(e) => {
+                    io.write_line("wrong");
+                    return Err(Verdict::WrongAnswer(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problem::Problem;
+
+    #[test]
+    fn maze_judge() {
+        let maze = Maze::parse("limit 3\n#####\n#S.E#\n#####\n; comment\n").unwrap();
+        let mut judge = MazeJudge::new(maze.clone());
+        assert_eq!(judge.respond("up"), Ok("wall"));
+        assert_eq!(judge.respond("right"), Ok("ok"));
+        assert!(judge.respond("no way out").is_err());
+        assert_eq!(judge.respond("right"), Ok("solved"));
+
+        let mut judge = MazeJudge::new(maze);
+        assert!(judge.respond("wait").is_err());
+        for _ in 0..3 {
+            assert_eq!(judge.respond("left"), Ok("wall"));
+        }
+        assert!(judge.respond("right").is_err());
+
+        let mut judge = MazeJudge::new(Maze::parse("S#E").unwrap());
+        assert_eq!(judge.respond("no way out"), Ok("solved"));
+    }
+
+    #[test]
+    fn amazing_mazes() {
+        let mut mazes = std::fs::read_dir(Problem::new("amazing").unwrap().input_dir())
+            .unwrap()
+            .map(|f| f.unwrap().path())
+            .filter(|p| p.extension() == Some(OsStr::new("maze")))
+            .collect::<Vec<_>>();
+        mazes.sort();
+
+        let judges = mazes
+            .iter()
+            .map(|path| (path.file_stem().unwrap().to_string_lossy().into_owned(), MazeJudge::new(Maze::load(path).unwrap())))
+            .collect();
+        crate::judge::test_interactive("amazing", judges);
+    }
+}
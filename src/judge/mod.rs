@@ -0,0 +1,145 @@
+//! Shared helpers to judge the output of a solution, used by both `cargo test` and the `kattis` helper.
+#[cfg(test)]
+use std::error::Error;
+use std::fmt;
+
+mod checker;
+mod diff;
+#[cfg(test)]
+mod generator;
+#[cfg(test)]
+mod interactive;
+#[cfg(test)]
+mod maze;
+mod runner;
+#[cfg(test)]
+mod stress;
+#[cfg(test)]
+mod validator;
+
+pub(crate) use checker::Checker;
+#[cfg(test)]
+pub(crate) use generator::{generate, Rng};
+pub(crate) use runner::{execute, megabytes, Execution, Limits};
+#[cfg(test)]
+pub(crate) use stress::{stress, stress_with};
+#[cfg(test)]
+pub(crate) use interactive::{interact, Channel, Judge};
+#[cfg(test)]
+pub(crate) use validator::{validate, Input, ValidationError};
+
+/// Outcome of running a solution on a single test case, named after the Kattis verdicts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Verdict {
+    Accepted,
+    WrongAnswer(String),
+    TimeLimitExceeded(String),
+    MemoryLimitExceeded(String),
+    RunTimeError(String),
+}
+
+impl Verdict {
+    /// The abbreviation Kattis uses for the verdict
+    pub fn code(&self) -> &'static str {
+        match self {
+            Verdict::Accepted => "AC",
+            Verdict::WrongAnswer(_) => "WA",
+            Verdict::TimeLimitExceeded(_) => "TLE",
+            Verdict::MemoryLimitExceeded(_) => "MLE",
+            Verdict::RunTimeError(_) => "RTE",
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Accepted => write!(f, "{}", self.code()),
+            Verdict::WrongAnswer(e)
+            | Verdict::TimeLimitExceeded(e)
+            | Verdict::MemoryLimitExceeded(e)
+            | Verdict::RunTimeError(e) => write!(f, "{} ({})", self.code(), e),
+        }
+    }
+}
+
+/// Runs `solve` on every sample in `input/<problem>/` and compares the output with the answer using the checker of
+/// the problem metadata. Every sample is reported separately, the test fails after all samples have been run.
+#[cfg(test)]
+pub(crate) fn test_samples<F>(problem: &str, solve: F)
+where
+    F: Fn(&[u8], &mut Vec<u8>) -> Result<(), Box<dyn Error>>,
+{
+    let metadata = crate::problem::Problem::new(problem).unwrap().metadata().unwrap();
+    test_samples_with(problem, solve, |_, output, answer| metadata.checker.check(output, answer))
+}
+
+/// Same as [`test_samples`], but `compare` judges the output given the input, the output and the answer, for problems
+/// accepting more than one answer
+#[cfg(test)]
+pub(crate) fn test_samples_with<F, C>(problem: &str, solve: F, compare: C)
+where
+    F: Fn(&[u8], &mut Vec<u8>) -> Result<(), Box<dyn Error>>,
+    C: Fn(&str, &str, &str) -> Result<(), String>,
+{
+    use std::panic::{self, AssertUnwindSafe};
+
+    let samples = crate::problem::Problem::new(problem).unwrap().samples().unwrap();
+    assert!(!samples.is_empty(), "no samples found for {}", problem);
+
+    let mut failures = Vec::new();
+    for sample in &samples {
+        let input = std::fs::read_to_string(&sample.input).unwrap();
+        let mut output: Vec<u8> = Vec::new();
+
+        let res = match panic::catch_unwind(AssertUnwindSafe(|| solve(input.as_bytes(), &mut output))) {
+            Ok(Ok(())) => match &sample.answer {
+                Some(answer) => compare(&input, &String::from_utf8_lossy(&output), &std::fs::read_to_string(answer).unwrap())
+                    .map_err(|e| format!("wrong answer, {}", e)),
+                None => Ok(()),
+            },
+            Ok(Err(e)) => Err(format!("returned an error: {}", e)),
+            Err(_) => Err("panicked".to_string()),
+        };
+
+        match res {
+            Ok(()) => println!("{} sample {} ... ok", problem, sample.name),
+            Err(e) => {
+                println!("{} sample {} ... FAILED", problem, sample.name);
+                failures.push(format!("file: {:?}: {}", sample.input, e));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} samples failed:\n{}",
+        failures.len(),
+        samples.len(),
+        failures.join("\n")
+    );
+}
+
+/// Compiles the solution like Kattis does and lets it talk to every judge within the limits of the problem metadata,
+/// reporting every judge separately
+#[cfg(test)]
+pub(crate) fn test_interactive<J: Judge>(problem: &str, judges: Vec<(String, J)>) {
+    let solution = crate::problem::Problem::new(problem).unwrap();
+    let (limits, binary) = (solution.metadata().unwrap().limits, solution.compile().unwrap());
+    assert!(!judges.is_empty(), "no judges given for {}", problem);
+
+    let mut failures = Vec::new();
+    for (name, mut judge) in judges {
+        let command = std::process::Command::new(&binary);
+        let (verdict, transcript) = interact(command, &mut judge, &limits).unwrap();
+
+        if verdict == Verdict::Accepted {
+            println!("{} judge {} ... ok", problem, name);
+        } else {
+            println!("{} judge {} ... FAILED", problem, name);
+            failures.push(format!("judge {}: {}, last lines of the interaction:\n{}", name, verdict, transcript));
+        }
+    }
+
+    assert!(failures.is_empty(), "{} judges failed:\n{}", failures.len(), failures.join("\n"));
+}
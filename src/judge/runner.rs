@@ -0,0 +1,240 @@
+//! Runs a compiled solution with the CPU time and memory limits of Kattis, using `setrlimit` and `wait4` on Linux.
+use std::io;
+#[cfg(target_os = "linux")]
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+#[cfg(target_os = "linux")]
+use std::thread;
+#[cfg(target_os = "linux")]
+use std::time::Instant;
+use std::time::Duration;
+
+use super::Verdict;
+
+pub(super) const SIGKILL: i32 = 9;
+pub(super) const SIGXCPU: i32 = 24;
+
+// The resource numbers and struct layouts below are those of Linux, other platforms number and lay them out differently
+#[cfg(target_os = "linux")]
+const RLIMIT_CPU: i32 = 0;
+#[cfg(target_os = "linux")]
+const RLIMIT_AS: i32 = 9;
+#[cfg(target_os = "linux")]
+const WNOHANG: i32 = 1;
+
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct RLimit {
+    cur: u64,
+    max: u64,
+}
+
+#[cfg(target_os = "linux")]
+#[repr(C)]
+#[derive(Default)]
+struct TimeVal {
+    sec: i64,
+    usec: i64,
+}
+
+#[cfg(target_os = "linux")]
+#[repr(C)]
+#[derive(Default)]
+struct RUsage {
+    utime: TimeVal,
+    stime: TimeVal,
+    maxrss: i64,
+    other: [i64; 13],
+}
+
+#[cfg(target_os = "linux")]
+extern "C" {
+    fn setrlimit(resource: i32, rlim: *const RLimit) -> i32;
+    fn wait4(pid: i32, status: *mut i32, options: i32, rusage: *mut RUsage) -> i32;
+}
+
+/// Resource limits of a single run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Limits {
+    /// CPU time limit
+    pub time: Duration,
+    /// Address space limit in bytes
+    pub memory: u64,
+}
+
+impl Default for Limits {
+    /// Kattis uses one second and 1024 MB for most problems
+    fn default() -> Self {
+        Limits { time: Duration::from_secs(1), memory: 1024 * 1024 * 1024 }
+    }
+}
+
+/// How the process ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Termination {
+    Exited(i32),
+    Signaled(i32),
+    /// Killed by us because it was sleeping or blocked for far longer than the time limit
+    WallTimeout,
+}
+
+/// Resource usage of a finished run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Execution {
+    pub termination: Termination,
+    pub wall: Duration,
+    pub cpu: Duration,
+    /// Peak resident set size in bytes
+    pub peak_rss: u64,
+}
+
+impl Execution {
+    /// The verdict if the run failed before its output could be judged, `stderr` is used to recognise failed
+    /// allocations which abort the process
+    pub fn verdict(&self, limits: &Limits, stderr: &str) -> Option<Verdict> {
+        let out_of_memory = self.peak_rss > limits.memory || stderr.contains("memory allocation of");
+
+        match self.termination {
+            Termination::WallTimeout => Some(Verdict::TimeLimitExceeded(format!("killed after {:.2}s wall time", self.wall.as_secs_f64()))),
+            _ if self.cpu > limits.time => Some(Verdict::TimeLimitExceeded(format!(
+                "{:.3}s cpu time, the limit is {:.3}s",
+                self.cpu.as_secs_f64(),
+                limits.time.as_secs_f64()
+            ))),
+            Termination::Signaled(signal @ SIGXCPU) | Termination::Signaled(signal @ SIGKILL) if !out_of_memory => {
+                Some(Verdict::TimeLimitExceeded(format!("killed by {}", signal_name(signal))))
+            }
+            _ if out_of_memory => Some(Verdict::MemoryLimitExceeded(format!("peak {}", megabytes(self.peak_rss)))),
+            Termination::Signaled(signal) => Some(Verdict::RunTimeError(format!("killed by {}", signal_name(signal)))),
+            Termination::Exited(0) => None,
+            Termination::Exited(code) => Some(Verdict::RunTimeError(format!("exit code {}", code))),
+        }
+    }
+}
+
+/// Wall time after which a run is over, a solution sleeping or blocked for that long will not finish in time
+#[cfg(any(test, target_os = "linux"))]
+pub(super) fn wall_limit(limits: &Limits) -> Duration {
+    limits.time * 3 + Duration::from_secs(1)
+}
+
+/// Applies the limits to the process spawned by the command. The soft CPU limit sends SIGXCPU, the hard limit a second
+/// later SIGKILL.
+#[cfg(target_os = "linux")]
+pub(super) fn apply_limits(command: &mut Command, limits: &Limits) -> io::Result<()> {
+    let cpu_seconds = limits.time.as_secs() + 1;
+    let memory = limits.memory;
+
+    // Safety: only async-signal-safe functions are called between fork and exec
+    unsafe {
+        command.pre_exec(move || {
+            if setrlimit(RLIMIT_CPU, &RLimit { cur: cpu_seconds, max: cpu_seconds + 1 }) != 0
+                || setrlimit(RLIMIT_AS, &RLimit { cur: memory, max: memory }) != 0
+            {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    Ok(())
+}
+
+/// The limits are applied with Linux specific system calls, elsewhere a run would silently get the wrong limits
+#[cfg(not(target_os = "linux"))]
+pub(super) fn apply_limits(command: &mut Command, limits: &Limits) -> io::Result<()> {
+    let _ = (command, limits);
+    Err(io::Error::new(io::ErrorKind::Unsupported, "running solutions with time and memory limits is only supported on Linux"))
+}
+
+/// Spawns the command with the limits applied and waits for it to finish. The stdio of the command should be set by
+/// the caller, pipes which are not read concurrently can block the process.
+#[cfg(target_os = "linux")]
+pub(crate) fn execute(mut command: Command, limits: &Limits) -> io::Result<Execution> {
+    apply_limits(&mut command, limits)?;
+
+    let started = Instant::now();
+    let mut child = command.spawn()?;
+    let pid = child.id() as i32;
+    let wall_limit = wall_limit(limits);
+
+    let mut status = 0;
+    let mut usage = RUsage::default();
+    let mut killed = false;
+
+    loop {
+        // Safety: status and usage are valid for writes, the pid belongs to our own unreaped child
+        match unsafe { wait4(pid, &mut status, WNOHANG, &mut usage) } {
+            0 => {
+                if !killed && started.elapsed() > wall_limit {
+                    child.kill()?;
+                    killed = true;
+                }
+                thread::sleep(Duration::from_millis(1));
+            }
+            -1 => return Err(io::Error::last_os_error()),
+            _ => break,
+        }
+    }
+
+    let termination = if killed {
+        Termination::WallTimeout
+    } else if status & 0x7f == 0 {
+        Termination::Exited((status >> 8) & 0xff)
+    } else {
+        Termination::Signaled(status & 0x7f)
+    };
+
+    let time = |t: &TimeVal| Duration::from_secs(t.sec as u64) + Duration::from_micros(t.usec as u64);
+    Ok(Execution {
+        termination,
+        wall: started.elapsed(),
+        cpu: time(&usage.utime) + time(&usage.stime),
+        peak_rss: usage.maxrss as u64 * 1024,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn execute(mut command: Command, limits: &Limits) -> io::Result<Execution> {
+    apply_limits(&mut command, limits)?;
+    unreachable!("the limits can only be applied on Linux")
+}
+
+pub(super) fn signal_name(signal: i32) -> String {
+    match signal {
+        4 => "SIGILL".to_string(),
+        6 => "SIGABRT".to_string(),
+        7 => "SIGBUS".to_string(),
+        8 => "SIGFPE".to_string(),
+        9 => "SIGKILL".to_string(),
+        11 => "SIGSEGV".to_string(),
+        24 => "SIGXCPU".to_string(),
+        n => format!("signal {}", n),
+    }
+}
+
+pub(crate) fn megabytes(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    fn sh(script: &str, limits: &Limits) -> Execution {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        execute(command, limits).unwrap()
+    }
+
+    #[test]
+    fn limits() {
+        let limits = Limits { time: Duration::from_millis(200), ..Limits::default() };
+
+        assert_eq!(sh("exit 0", &limits).verdict(&limits, ""), None);
+        assert_eq!(sh("exit 3", &limits).verdict(&limits, ""), Some(Verdict::RunTimeError("exit code 3".to_string())));
+        assert_eq!(sh("kill -SEGV $$", &limits).verdict(&limits, ""), Some(Verdict::RunTimeError("killed by SIGSEGV".to_string())));
+        assert!(matches!(sh("while :; do :; done", &limits).verdict(&limits, ""), Some(Verdict::TimeLimitExceeded(_))));
+        assert!(matches!(sh("sleep 5", &limits).termination, Termination::WallTimeout));
+        assert!(matches!(sh("exit 134", &limits).verdict(&limits, "memory allocation of 8 bytes failed"), Some(Verdict::MemoryLimitExceeded(_))));
+    }
+}
@@ -0,0 +1,102 @@
+//! Compares a solution with a brute force reference on random inputs, run by `kattis stress <problem>`.
+//!
+//! The amount of inputs, the seed and the maximum size can be set with the `KATTIS_STRESS_ITERATIONS`,
+//! `KATTIS_STRESS_SEED` and `KATTIS_STRESS_SIZE` environment variables. Without them a small, fixed set of inputs
+//! is used so `cargo test` stays fast and deterministic.
+use std::error::Error;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+
+use super::generator::{Generator, Rng};
+use super::Checker;
+use crate::problem::Problem;
+
+fn env(name: &str, default: u64) -> u64 {
+    std::env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+/// Runs the solve function on the input, panics and errors are turned into an error message
+fn run<F>(solve: &F, input: &str) -> Result<String, String>
+where
+    F: Fn(&[u8], &mut Vec<u8>) -> Result<(), Box<dyn Error>>,
+{
+    let mut output = Vec::new();
+    match panic::catch_unwind(AssertUnwindSafe(|| solve(input.as_bytes(), &mut output))) {
+        Ok(Ok(())) => Ok(String::from_utf8_lossy(&output).into_owned()),
+        Ok(Err(e)) => Err(format!("returned an error: {}", e)),
+        Err(_) => Err("panicked".to_string()),
+    }
+}
+
+/// First `input/<problem>/stress-N.in` which does not exist yet, run with the samples from then on
+fn failure_path(problem: &str) -> PathBuf {
+    let dir = Problem::new(problem).unwrap().input_dir();
+    fs::create_dir_all(&dir).unwrap();
+    (1..).map(|n| dir.join(format!("stress-{}.in", n))).find(|p| !p.exists()).unwrap()
+}
+
+/// Compares the output of `solve` and `brute` with the exact checker, see [`stress_with`]
+pub(crate) fn stress<G, F, B>(problem: &str, generator: G, solve: F, brute: B)
+where
+    G: Generator,
+    F: Fn(&[u8], &mut Vec<u8>) -> Result<(), Box<dyn Error>>,
+    B: Fn(&[u8], &mut Vec<u8>) -> Result<(), Box<dyn Error>>,
+{
+    stress_with(problem, generator, solve, brute, |_, output, expected| Checker::Exact.check(output, expected))
+}
+
+/// Runs `solve` and `brute` on generated inputs until `compare(input, output, expected)` fails. The failing input is
+/// stored in `input/<problem>/stress-N.in` so it can be debugged, and becomes a sample once it has an answer.
+pub(crate) fn stress_with<G, F, B, C>(problem: &str, mut generator: G, solve: F, brute: B, compare: C)
+where
+    G: Generator,
+    F: Fn(&[u8], &mut Vec<u8>) -> Result<(), Box<dyn Error>>,
+    B: Fn(&[u8], &mut Vec<u8>) -> Result<(), Box<dyn Error>>,
+    C: Fn(&str, &str, &str) -> Result<(), String>,
+{
+    let iterations = env("KATTIS_STRESS_ITERATIONS", 100);
+    let seed = env("KATTIS_STRESS_SEED", 0);
+    let max_size = env("KATTIS_STRESS_SIZE", 10).max(1);
+
+    for i in 0..iterations {
+        // Every input gets its own seed, so a single failure can be reproduced without the ones before it
+        let mut rng = Rng::new(seed.wrapping_add(i));
+        let size = rng.range(1..=max_size as usize);
+        let input = generator.generate(&mut rng, size);
+
+        let expected = match run(&brute, &input) {
+            Ok(expected) => expected,
+            Err(e) => panic!("brute force of {} {} on input:\n{}", problem, e, input),
+        };
+
+        if let Err(e) = run(&solve, &input).and_then(|output| compare(&input, &output, &expected)) {
+            let path = failure_path(problem);
+            fs::write(&path, &input).unwrap();
+            panic!(
+                "{} failed on iteration {} (seed {}, size {}): {}\ninput saved to {:?}:\n{}\nexpected:\n{}",
+                problem,
+                i,
+                seed.wrapping_add(i),
+                size,
+                e,
+                path,
+                input,
+                expected
+            );
+        }
+    }
+
+    println!("{} passed {} stress tests with seed {}", problem, iterations, seed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failure_path_in_input() {
+        let dir = Problem::new("typo").unwrap().input_dir();
+        assert_eq!(failure_path("typo"), dir.join("stress-1.in"));
+    }
+}
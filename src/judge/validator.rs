@@ -0,0 +1,231 @@
+//! Checks that inputs respect the constraints of a problem, run by `kattis validate`.
+//!
+//! A validator reads the input line by line with [`Input`], every value is checked against its range while it is
+//! read and the format is strict: tokens are separated by a single space, lines end with `\n` and nothing may
+//! follow the last line. A validator of a problem looks like
+//!
+//! ```ignore
+//! fn validate(input: &str) -> Result<(), ValidationError> {
+//!     let mut input = Input::new(input);
+//!     let mut line = input.line(2)?;
+//!     let n = line.int(1..=100_000)?;
+//!     let _k = line.int(0..=n)?;
+//!     for _ in 0..n {
+//!         input.line(1)?.word(1..=10, |c| c.is_ascii_lowercase())?;
+//!     }
+//!     input.eof()
+//! }
+//! ```
+//!
+//! and is registered with [`validate`], which runs it over every `.in` file of the problem.
+use std::fmt;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::problem::Problem;
+
+/// A violated constraint, `line` is 1-based
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ValidationError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// The input of a problem, read one line at a time
+pub(crate) struct Input<'a> {
+    rest: &'a str,
+    line: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Input { rest: input, line: 0 }
+    }
+
+    /// An error on the last line which has been read
+    pub fn error(&self, message: impl Into<String>) -> ValidationError {
+        ValidationError { line: self.line.max(1), message: message.into() }
+    }
+
+    /// Fails with the message unless the condition holds, for constraints between values like distinct edges
+    pub fn check(&self, condition: bool, message: impl FnOnce() -> String) -> Result<(), ValidationError> {
+        if condition {
+            Ok(())
+        } else {
+            Err(self.error(message()))
+        }
+    }
+
+    /// Reads the next line, which should consist of exactly `tokens` tokens
+    pub fn line(&mut self, tokens: usize) -> Result<Line<'a>, ValidationError> {
+        if self.rest.is_empty() {
+            return Err(ValidationError { line: self.line + 1, message: "unexpected end of the input".to_string() });
+        }
+        self.line += 1;
+
+        let (text, rest) = match self.rest.find('\n') {
+            Some(end) => (&self.rest[..end], &self.rest[end + 1..]),
+            None => return Err(self.error("the line does not end with a newline")),
+        };
+        self.rest = rest;
+
+        if text.contains('\r') {
+            return Err(self.error("the line ends with \\r\\n"));
+        }
+        let split: Vec<&str> = if text.is_empty() { Vec::new() } else { text.split(' ').collect() };
+        if split.iter().any(|token| token.is_empty()) {
+            return Err(self.error("tokens should be separated by a single space, without spaces at the start or end"));
+        }
+        if split.len() != tokens {
+            return Err(self.error(format!("expected {} tokens, found {}", tokens, split.len())));
+        }
+
+        Ok(Line { tokens: split.into_iter(), line: self.line })
+    }
+
+    /// Checks that the whole input has been read
+    pub fn eof(&self) -> Result<(), ValidationError> {
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { line: self.line + 1, message: "expected the end of the input".to_string() })
+        }
+    }
+}
+
+/// The tokens of a single line, read from left to right
+pub(crate) struct Line<'a> {
+    tokens: std::vec::IntoIter<&'a str>,
+    line: usize,
+}
+
+impl<'a> Line<'a> {
+    fn error(&self, message: String) -> ValidationError {
+        ValidationError { line: self.line, message }
+    }
+
+    fn token(&mut self) -> Result<&'a str, ValidationError> {
+        // `Input::line` already checked the amount of tokens
+        self.tokens.next().ok_or_else(|| self.error("the line has fewer tokens than read".to_string()))
+    }
+
+    /// An integer in the range, without a `+` or leading zeros
+    pub fn int<T>(&mut self, range: RangeInclusive<T>) -> Result<T, ValidationError>
+    where
+        T: FromStr + PartialOrd + fmt::Display,
+    {
+        let token = self.token()?;
+        let digits = token.strip_prefix('-').unwrap_or(token);
+        let canonical = !digits.is_empty() && digits.bytes().all(|c| c.is_ascii_digit()) && (digits == "0" || !digits.starts_with('0'));
+        if !canonical || token == "-0" {
+            return Err(self.error(format!("'{}' is not an integer", token)));
+        }
+
+        match token.parse::<T>() {
+            Ok(n) if range.contains(&n) => Ok(n),
+            _ => Err(self.error(format!("{} is not in the range {}..={}", token, range.start(), range.end()))),
+        }
+    }
+
+    /// A decimal number in the range with at most `decimals` digits after the point
+    pub fn float(&mut self, range: RangeInclusive<f64>, decimals: usize) -> Result<f64, ValidationError> {
+        let token = self.token()?;
+        let unsigned = token.strip_prefix('-').unwrap_or(token);
+        let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let digits = |s: &str| !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit());
+        if !digits(int) || (unsigned.contains('.') && !digits(frac)) {
+            return Err(self.error(format!("'{}' is not a decimal number", token)));
+        }
+        if frac.len() > decimals {
+            return Err(self.error(format!("{} has more than {} decimals", token, decimals)));
+        }
+
+        let n: f64 = token.parse().unwrap();
+        if !range.contains(&n) {
+            return Err(self.error(format!("{} is not in the range {}..={}", token, range.start(), range.end())));
+        }
+        Ok(n)
+    }
+
+    /// A word with a length in the range, consisting of characters accepted by `allowed`
+    pub fn word(&mut self, len: RangeInclusive<usize>, allowed: impl Fn(char) -> bool) -> Result<&'a str, ValidationError> {
+        let token = self.token()?;
+        if let Some(c) = token.chars().find(|&c| !allowed(c)) {
+            return Err(self.error(format!("'{}' contains the invalid character '{}'", token, c)));
+        }
+        if !len.contains(&token.chars().count()) {
+            return Err(self.error(format!("the length of '{}' is not in the range {}..={}", token, len.start(), len.end())));
+        }
+        Ok(token)
+    }
+}
+
+/// Registers the validator of a problem and runs it over every `.in` file in `input/<problem>/`, including the
+/// generated ones in `input/<problem>/gen/`. All inputs are checked before the test fails.
+pub(crate) fn validate<V>(problem: &str, validator: V)
+where
+    V: Fn(&str) -> Result<(), ValidationError>,
+{
+    let mut samples = Problem::new(problem).unwrap().samples().unwrap();
+    samples.extend(Problem::new(problem).unwrap().generated().unwrap());
+    assert!(!samples.is_empty(), "no inputs found for {}", problem);
+
+    let mut failures = Vec::new();
+    for sample in &samples {
+        let input = fs::read_to_string(&sample.input).unwrap();
+        if let Err(e) = validator(&input) {
+            failures.push(format!("{}: {}", sample.input.display(), e));
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!("{} of {} inputs of {} are invalid:\n{}", failures.len(), samples.len(), problem, failures.join("\n"));
+    }
+    println!("all {} inputs of {} are valid", samples.len(), problem);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(input: &str) -> Result<(), ValidationError> {
+        let mut input = Input::new(input);
+        let mut line = input.line(2)?;
+        let n = line.int(1..=3)?;
+        line.float(-1.0..=1.0, 2)?;
+        for _ in 0..n {
+            let word = input.line(1)?.word(1..=3, |c| c.is_ascii_lowercase())?;
+            input.check(word != "no", || format!("{} is not allowed", word))?;
+        }
+        input.eof()
+    }
+
+    #[test]
+    fn constraints() {
+        let error = |line, message: &str| Err(ValidationError { line, message: message.to_string() });
+
+        assert_eq!(validate("2 -0.25\nab\nc\n"), Ok(()));
+        assert_eq!(validate("2 1\nab\n"), error(3, "unexpected end of the input"));
+        assert_eq!(validate("1 1\nab\nc\n"), error(3, "expected the end of the input"));
+        assert_eq!(validate("1 1\nab"), error(2, "the line does not end with a newline"));
+        assert_eq!(validate("1 1\r\nab\n"), error(1, "the line ends with \\r\\n"));
+        assert_eq!(validate("1  1\nab\n"), error(1, "tokens should be separated by a single space, without spaces at the start or end"));
+        assert_eq!(validate("1\nab\n"), error(1, "expected 2 tokens, found 1"));
+        assert_eq!(validate("4 1\n"), error(1, "4 is not in the range 1..=3"));
+        assert_eq!(validate("01 1\n"), error(1, "'01' is not an integer"));
+        assert_eq!(validate("+1 1\n"), error(1, "'+1' is not an integer"));
+        assert_eq!(validate("1 0.125\n"), error(1, "0.125 has more than 2 decimals"));
+        assert_eq!(validate("1 1.\n"), error(1, "'1.' is not a decimal number"));
+        assert_eq!(validate("1 -1.5\n"), error(1, "-1.5 is not in the range -1..=1"));
+        assert_eq!(validate("1 1\nAb\n"), error(2, "'Ab' contains the invalid character 'A'"));
+        assert_eq!(validate("1 1\nabcd\n"), error(2, "the length of 'abcd' is not in the range 1..=3"));
+        assert_eq!(validate("1 1\nno\n"), error(2, "no is not allowed"));
+    }
+}
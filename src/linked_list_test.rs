@@ -1,9 +1,9 @@
 use std::collections::HashMap;
 use std::cmp::Ordering;
-use std::io::{self, BufRead};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
 use std::rc::Rc;
 use std::cell::RefCell;
-use std::ops::Deref;
 use std::fmt;
 
 #[derive(Eq, PartialEq)]
@@ -77,8 +77,14 @@ impl PartialOrd for Patient {
     }
 }
 
-fn main() {
+/// main entry point of program when invoked directly in the terminal
+#[allow(dead_code)]
+fn main() -> Result<(), Box<dyn Error>> {
+    solve(io::stdin().lock(), io::stdout().lock())
+}
 
+/// Not a Kattis problem but a test of the linked list used in doctor_kattis, the input is ignored
+fn solve(_input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
     let mut patients = HashMap::new();
     patients.insert("Test", Rc::new(RefCell::new(LinkedEdge {
         default: false,
@@ -122,6 +128,15 @@ fn main() {
     linked_list.add(patients.get("Test2").unwrap().clone());
     patients.remove("Test").unwrap().borrow_mut().remove().unwrap();
 
-    println!("{:?}", linked_list);
+    writeln!(&mut w, "{:?}", linked_list).unwrap();
+
+    Ok(())
+}
 
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn linked_list_test_sampleinputs() {
+        crate::judge::test_samples("linked_list_test", |input, output| super::solve(input, output));
+    }
+}
@@ -68,13 +68,30 @@ mod earlywinter;
 mod greetings2;
 mod tarifa;
 mod twosum;
+mod amazing;
+mod different;
+mod bracket_sequence;
+mod doctor_kattis;
+mod chineseremainder;
+mod primepath;
+mod firestation;
+mod linked_list_test;
 
 #[allow(dead_code)]
 pub(crate) mod snippets;
 
 #[allow(unused_variables, unused_mut)]
 mod default;
+#[allow(unused_variables, unused_mut)]
+mod templates;
+
+mod bundle;
+mod cli;
+mod judge;
+mod metadata;
+mod problem;
+mod zip;
 
 fn main() {
-    println!("This program should not be run directly. Use ./kattis to run a specific problem.");
+    std::process::exit(cli::main(std::env::args().skip(1).collect()));
 }
@@ -105,28 +105,40 @@ fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use crate::judge::{Input, Rng, ValidationError};
 
     #[test]
     fn megainversions_sampleinputs() {
-        for mut file in std::fs::read_dir("input/megainversions")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert_eq!(
-                std::str::from_utf8(&output_writer).unwrap().trim(),
-                std::fs::read_to_string(&file).unwrap().trim(),
-                "file: {:?}",
-                file
-            );
+        crate::judge::test_samples("megainversions", |input, output| super::solve(input, output));
+    }
+
+    /// A sequence of `size` numbers, sometimes strictly decreasing to get the largest amount of inversions
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let sequence: Vec<String> = if rng.chance(0.25) {
+            (1..=size).rev().map(|x| x.to_string()).collect()
+        } else {
+            (0..size).map(|_| rng.range(1..=size).to_string()).collect()
+        };
+        format!("{}\n{}\n", size, sequence.join(" "))
+    }
+
+    #[test]
+    fn megainversions_generate() {
+        crate::judge::generate("megainversions", 100_000, generate);
+    }
+
+    fn validate(input: &str) -> Result<(), ValidationError> {
+        let mut input = Input::new(input);
+        let n = input.line(1)?.int(1..=100_000usize)?;
+        let mut line = input.line(n)?;
+        for _ in 0..n {
+            line.int(1..=n)?;
         }
+        input.eof()
+    }
+
+    #[test]
+    fn megainversions_validate() {
+        crate::judge::validate("megainversions", validate);
     }
 }
@@ -0,0 +1,261 @@
+//! Metadata of a problem stored in `input/<problem>/problem.toml`, parsed by hand since the crate has no dependencies.
+//!
+//! Only the small part of TOML the file needs is supported: `key = value` lines with strings, numbers, booleans and
+//! single line arrays, and `#` comments. Every field is optional:
+//!
+//! ```toml
+//! title = "Rafting"
+//! time_limit = 2          # CPU time in seconds
+//! memory_limit = 1024     # MB
+//! checker = "float"       # exact, tokens, case-insensitive or float
+//! tolerance = 1e-6        # absolute or relative error accepted by the float checker
+//! interactive = false
+//! difficulty = 3.4
+//! tags = ["geometry", "line segments"]
+//! ```
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::judge::{Checker, Limits};
+
+/// Larger limits are typos, in seconds and MB, and would overflow a `Duration` or the amount of bytes
+const MAX_TIME_LIMIT: f64 = 3600.0;
+const MAX_MEMORY_LIMIT: f64 = 1024.0 * 1024.0;
+
+fn positive(n: f64, max: f64) -> bool {
+    n.is_finite() && n > 0.0 && n <= max
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct Metadata {
+    pub title: Option<String>,
+    pub limits: Limits,
+    pub checker: Checker,
+    /// Interactive problems talk to a judge instead of reading a sample
+    pub interactive: bool,
+    /// Difficulty as shown on open.kattis.com
+    pub difficulty: Option<f64>,
+    /// Algorithms and techniques used by the solution, found by `kattis search`
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    String(String),
+    Number(f64),
+    Bool(bool),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    fn kind(&self) -> &'static str {
+        match self {
+            Value::String(_) => "a string",
+            Value::Number(_) => "a number",
+            Value::Bool(_) => "a boolean",
+            Value::Array(_) => "an array",
+        }
+    }
+}
+
+/// Template written by `kattis new`, every field apart from the checker and `interactive` has its default value
+pub(crate) fn template(title: &str, checker: &str, interactive: bool) -> String {
+    format!(
+        "title = \"{}\"
+time_limit = 1          # CPU time in seconds
+memory_limit = 1024     # MB
+checker = {:<13} # exact, tokens, case-insensitive or float (with a tolerance)
+interactive = {}
+# difficulty = 1.5
+tags = []
+",
+        title,
+        format!("\"{}\"", checker),
+        interactive
+    )
+}
+
+impl Metadata {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut metadata = Metadata::default();
+        let mut tolerance = None;
+        let mut seen = HashSet::new();
+
+        for (n, line) in text.lines().enumerate() {
+            let err = |e: String| format!("line {}: {}", n + 1, e);
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), parse_value(value.trim()).map_err(err)?),
+                None => return Err(err(format!("expected `key = value`, got '{}'", line))),
+            };
+            if !seen.insert(key.to_string()) {
+                return Err(err(format!("duplicate key '{}'", key)));
+            }
+
+            let expected = |kind: &str| err(format!("{} should be {}, not {}", key, kind, value.kind()));
+            match (key, &value) {
+                ("title", Value::String(s)) => metadata.title = Some(s.clone()),
+                ("time_limit", Value::Number(n)) if positive(*n, MAX_TIME_LIMIT) => metadata.limits.time = Duration::from_secs_f64(*n),
+                ("memory_limit", Value::Number(n)) if positive(*n, MAX_MEMORY_LIMIT) => metadata.limits.memory = (*n * 1024.0 * 1024.0) as u64,
+                ("checker", Value::String(s)) => metadata.checker = s.parse().map_err(err)?,
+                ("tolerance", Value::Number(n)) if *n >= 0.0 => tolerance = Some(*n),
+                ("interactive", Value::Bool(b)) => metadata.interactive = *b,
+                ("difficulty", Value::Number(n)) => metadata.difficulty = Some(*n),
+                ("tags", Value::Array(tags)) => {
+                    for tag in tags {
+                        match tag {
+                            Value::String(tag) => metadata.tags.push(tag.clone()),
+                            _ => return Err(expected("an array of strings")),
+                        }
+                    }
+                }
+                ("time_limit" | "memory_limit" | "tolerance", Value::Number(_)) => return Err(expected("a positive number")),
+                ("title" | "checker", _) => return Err(expected("a string")),
+                ("time_limit" | "memory_limit" | "tolerance" | "difficulty", _) => return Err(expected("a number")),
+                ("interactive", _) => return Err(expected("a boolean")),
+                ("tags", _) => return Err(expected("an array of strings")),
+                _ => return Err(err(format!("unknown key '{}'", key))),
+            }
+        }
+
+        if let Some(tolerance) = tolerance {
+            match metadata.checker {
+                Checker::Float { .. } => metadata.checker = Checker::float(tolerance),
+                checker => return Err(format!("a tolerance is only used by the float checker, not by {}", checker)),
+            }
+        }
+
+        Ok(metadata)
+    }
+}
+
+/// Removes a `#` comment, unless the `#` is part of a string
+fn strip_comment(line: &str) -> &str {
+    let (mut in_string, mut escaped) = (false, false);
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' if !escaped => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    line
+}
+
+fn parse_value(value: &str) -> Result<Value, String> {
+    if let Some(s) = value.strip_prefix('"') {
+        let mut string = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if chars.as_str().trim().is_empty() => return Ok(Value::String(string)),
+                '"' => return Err(format!("unexpected '{}' after a string", chars.as_str().trim())),
+                '\\' => match chars.next() {
+                    Some('n') => string.push('\n'),
+                    Some('t') => string.push('\t'),
+                    Some(c @ ('"' | '\\')) => string.push(c),
+                    c => return Err(format!("unsupported escape '\\{}'", c.map_or(String::new(), String::from))),
+                },
+                c => string.push(c),
+            }
+        }
+        return Err(format!("unterminated string {}", value));
+    }
+
+    if let Some(items) = value.strip_prefix('[') {
+        let items = items.strip_suffix(']').ok_or("arrays should be on a single line and end with ']'")?;
+        return split_items(items)
+            .into_iter()
+            .filter(|item| !item.trim().is_empty())
+            .map(|item| parse_value(item.trim()))
+            .collect::<Result<_, _>>()
+            .map(Value::Array);
+    }
+
+    match value {
+        "true" => Ok(Value::Bool(true)),
+        "false" => Ok(Value::Bool(false)),
+        _ => value.replace('_', "").parse().map(Value::Number).map_err(|_| format!("invalid value '{}'", value)),
+    }
+}
+
+/// Splits the items of an array on the commas which are not inside a string
+fn split_items(items: &str) -> Vec<&str> {
+    let mut res = Vec::new();
+    let (mut start, mut in_string, mut escaped) = (0, false, false);
+    for (i, c) in items.char_indices() {
+        match c {
+            '"' if !escaped => in_string = !in_string,
+            ',' if !in_string => {
+                res.push(&items[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    res.push(&items[start..]);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problem::Problem;
+
+    #[test]
+    fn parse() {
+        let metadata = Metadata::parse(
+            "# comment\ntitle = \"A # B\"\ntime_limit = 2.5\nmemory_limit = 2_048\nchecker = \"float\" # comment\n\
+             tolerance = 1e-6\ninteractive = true\ndifficulty = 3.4\ntags = [\"graphs\", \"a, b\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(metadata.title.as_deref(), Some("A # B"));
+        assert_eq!(metadata.limits, Limits { time: Duration::from_millis(2500), memory: 2048 * 1024 * 1024 });
+        assert_eq!(metadata.checker, Checker::float(1e-6));
+        assert!(metadata.interactive);
+        assert_eq!(metadata.difficulty, Some(3.4));
+        assert_eq!(metadata.tags, vec!["graphs".to_string(), "a, b".to_string()]);
+
+        assert_eq!(Metadata::parse("").unwrap(), Metadata::default());
+        assert_eq!(Metadata::parse(&template("Title", "exact", false)).unwrap(), Metadata { title: Some("Title".to_string()), ..Metadata::default() });
+        let metadata = Metadata::parse(&template("Title", "float", true)).unwrap();
+        assert_eq!((metadata.checker, metadata.interactive), (Checker::float(1e-6), true));
+
+        assert!(Metadata::parse("time_limit = -1").is_err());
+        assert!(Metadata::parse("time_limit = \"1\"").is_err());
+        assert!(Metadata::parse("tolerance = 1e-6").is_err());
+        assert!(Metadata::parse("title = \"a\"\ntitle = \"b\"").is_err());
+        assert!(Metadata::parse("titel = \"a\"").is_err());
+        assert!(Metadata::parse("tags = [\"a\",\n\"b\"]").is_err());
+        assert!(Metadata::parse("title = \"a").is_err());
+    }
+
+    #[test]
+    fn limit_bounds() {
+        for line in &["time_limit = inf", "time_limit = 1e30", "time_limit = nan", "time_limit = 0", "memory_limit = inf", "memory_limit = 1e30"] {
+            let error = Metadata::parse(line).unwrap_err();
+            assert!(error.ends_with("should be a positive number, not a number"), "{}: {}", line, error);
+        }
+        assert_eq!(Metadata::parse("time_limit = 3600").unwrap().limits.time, Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn problem_files() {
+        for entry in std::fs::read_dir(crate::problem::root().join("input")).unwrap() {
+            let path = entry.unwrap().path();
+            if path.join("problem.toml").is_file() {
+                let problem = Problem::new(&path.file_name().unwrap().to_string_lossy()).unwrap();
+                if let Err(e) = problem.metadata() {
+                    panic!("{}", e);
+                }
+            }
+        }
+    }
+}
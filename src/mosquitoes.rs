@@ -2,93 +2,7 @@ use std::error::Error;
 use std::io::{self, BufRead, Write};
 use std::cmp;
 
-/// This file is based on the Handbook of geometry for competitive programmers by Victor Lecomte
-
-use std::ops::{Div, Mul, Sub, Add, Neg};
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Clone)]
-pub struct Point<T> {
-    pub x: T,
-    pub y: T,
-}
-
-impl<T: Add<Output=T> + Copy> Add for &Point<T> {
-    type Output = Point<T>;
-
-    fn add(self, other: Self) -> Point<T> {
-        Point {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
-    }
-}
-
-impl<T: Sub<Output=T> + Copy> Sub for &Point<T> {
-    type Output = Point<T>;
-
-    fn sub(self, other: Self) -> Point<T> {
-        Point {
-            x: self.x - other.x,
-            y: self.y - other.y,
-        }
-    }
-}
-
-impl<T: Mul<Output=T> + Copy> Mul<T> for &Point<T> {
-    type Output = Point<T>;
-
-    fn mul(self, rhs: T) -> Point<T> {
-        Point {
-            x: self.x * rhs,
-            y: self.y * rhs
-        }
-    }
-}
-
-impl<T: Div<Output=T> + Copy> Div<T> for &Point<T> {
-    type Output = Point<T>;
-
-    fn div(self, rhs: T) -> Point<T> {
-        Point {
-            x: self.x / rhs,
-            y: self.y / rhs
-        }
-    }
-}
-
-impl<T: Neg<Output=T> + Copy> Neg for &Point<T> {
-    type Output = Point<T>;
-
-    fn neg(self) -> Point<T> {
-        Point {
-            x: -self.x,
-            y: -self.y
-        }
-    }
-}
-
-impl<T: Add<Output=T> + Mul<Output=T> + Sub<Output=T> + Copy> Point<T> {
-    pub fn square(&self) -> T {
-        return self.x * self.x + self.y * self.y;
-    }
-}
-
-impl Point<f64> {
-    pub fn abs(&self) -> f64 {
-        self.square().sqrt()
-    }
-}
-
-pub struct Circle<'a, T> {
-    pub center: &'a Point<T>,
-    pub radius: T
-}
-
-impl<> Circle<'_, f64> {
-    pub fn in_circle(&self, p: &Point<f64>) -> bool {
-        (p - self.center).abs() < self.radius
-    }
-}
+use crate::snippets::geometry::*;
 
 /// main entry point of program when invoked directly in the terminal
 #[allow(dead_code)]
@@ -160,28 +74,8 @@ fn count_in_circle(c: &Circle<f64>, mosquitoes: &Vec<Point<f64>>) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn mosquitoes_sampleinputs() {
-        for mut file in std::fs::read_dir("input/mosquitoes")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert_eq!(
-                std::str::from_utf8(&output_writer).unwrap().trim(),
-                std::fs::read_to_string(&file).unwrap().trim(),
-                "file: {:?}",
-                file
-            );
-        }
+        crate::judge::test_samples("mosquitoes", |input, output| super::solve(input, output));
     }
 }
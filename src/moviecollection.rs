@@ -93,28 +93,43 @@ fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use crate::judge::{Input, Rng, ValidationError};
 
     #[test]
     fn moviecollection_sampleinputs() {
-        for mut file in std::fs::read_dir("input/moviecollection")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert_eq!(
-                std::str::from_utf8(&output_writer).unwrap().trim(),
-                std::fs::read_to_string(&file).unwrap().trim(),
-                "file: {:?}",
-                file
-            );
+        crate::judge::test_samples("moviecollection", |input, output| super::solve(input, output));
+    }
+
+    /// A single test case with `size` movies and `size` requests, sometimes always asking for the bottom movie
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        // Every request moves the bottom movie to the top, so the bottom ones are size, size - 1, ..., 1, size, ...
+        let bottom = rng.chance(0.25);
+        let requests: Vec<String> =
+            (0..size).map(|i| if bottom { size - i % size } else { rng.range(1..=size) }.to_string()).collect();
+        format!("1\n{} {}\n{}\n", size, size, requests.join(" "))
+    }
+
+    #[test]
+    fn moviecollection_generate() {
+        crate::judge::generate("moviecollection", 100_000, generate);
+    }
+
+    fn validate(input: &str) -> Result<(), ValidationError> {
+        let mut input = Input::new(input);
+        let cases = input.line(1)?.int(1..=100usize)?;
+        for _ in 0..cases {
+            let mut line = input.line(2)?;
+            let (m, r) = (line.int(1..=100_000usize)?, line.int(1..=100_000usize)?);
+            let mut line = input.line(r)?;
+            for _ in 0..r {
+                line.int(1..=m)?;
+            }
         }
+        input.eof()
+    }
+
+    #[test]
+    fn moviecollection_validate() {
+        crate::judge::validate("moviecollection", validate);
     }
 }
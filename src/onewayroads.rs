@@ -2,6 +2,8 @@ use std::collections::HashSet;
 use std::error::Error;
 use std::io::{self, BufRead, Write};
 
+use crate::snippets::io::Scanner;
+
 /// main entry point of program when invoked directly in the terminal
 #[allow(dead_code)]
 fn main() -> Result<(), Box<dyn Error>> {
@@ -24,18 +26,13 @@ impl Connection {
 }
 
 fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
-    let mut input_reader = input.lines().map(|l| {
-        let l = l.unwrap();
-        let mut l = l.split(' ');
-        (l.next().unwrap().parse::<usize>().unwrap(), l.next().unwrap().parse::<usize>().unwrap())
-    });
-
-    let (n, m) = input_reader.next().unwrap();
+    let mut scanner = Scanner::new(input);
+    let (n, m): (usize, usize) = scanner.tuple()?;
     let mut nodes: Vec<HashSet<Connection>> = vec![HashSet::new(); n];
 
     // Create graph
     for _ in 0..m {
-        let (a, b) = input_reader.next().unwrap();
+        let (a, b): (usize, usize) = scanner.tuple()?;
         (*nodes.get_mut(a-1).unwrap()).insert(Connection::Edge(b-1));
         (*nodes.get_mut(b-1).unwrap()).insert(Connection::Edge(a-1));
     }
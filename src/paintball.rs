@@ -1,6 +1,7 @@
 use std::error::Error;
 use std::io::{self, BufRead, Write};
-use std::cmp;
+
+use crate::snippets::flow::FlowGraph;
 
 /// main entry point of program when invoked directly in the terminal
 #[allow(dead_code)]
@@ -16,91 +17,128 @@ fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
 
     let (n, m) = lines.next().unwrap();
 
-    // Adjecency matrix, 0..n incoming, n..2*n receiving, 2*n source, 2*n+1 sink
-    let mut res_graph: Vec<Vec<i16>> = vec![vec![0; n * 2 + 2]; n * 2 + 2];
+    // 0..n incoming, n..2*n receiving, 2*n source, 2*n+1 sink
+    let mut graph: FlowGraph<usize> = FlowGraph::new(n * 2 + 2);
 
     for i in 0..n {
-        res_graph[2*n][i] = 1;
-        res_graph[n + i][2*n+1] = 1;
+        graph.add_edge(2*n, i, 1);
+        graph.add_edge(n + i, 2*n+1, 1);
     }
 
     for _ in 0..m {
         let (a, b) = lines.next().unwrap();
-        res_graph[a - 1][n + b - 1] = 1;
-        res_graph[b - 1][n + a - 1] = 1;
+        graph.add_edge(a - 1, n + b - 1, 1);
+        graph.add_edge(b - 1, n + a - 1, 1);
     }
 
-    let res = min_flow(&mut res_graph, 2*n, 2*n+1);
-    if res != n as i16 {
+    let res = graph.max_flow(2*n, 2*n+1);
+    if res != n {
         writeln!(&mut w, "Impossible").unwrap();
     } else {
-        'outloop: for i in 0..n {
-            for o in 0..n {
-                if res_graph[n + o][i] == 1 {
-                    writeln!(&mut w, "{}", o + 1).unwrap();
-                    continue 'outloop;
-                }
-            }
+        for i in 0..n {
+            let target = graph.edges_from(i).find(|edge| edge.flow() == 1).unwrap().to;
+            writeln!(&mut w, "{}", target - n + 1).unwrap();
         }
     }
 
     Ok(())
 }
 
-fn dfs(graph: &mut Vec<Vec<i16>>, visited: &mut Vec<bool>, s: usize, t: usize, min: i16) -> Option<i16> {
-    visited[s] = true;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::judge::Rng;
 
-    if s == t {
-        return Some(min);
+    #[test]
+    fn paintball_sampleinputs() {
+        crate::judge::test_samples_with("paintball", |input, output| super::solve(input, output), compare);
     }
 
-    for i in 0..graph.len() {
-        if !visited[i] && graph[s][i] > 0 {
-            if let Some(min_cap) = dfs(graph, visited, i, t, cmp::min(min, graph[s][i])) {
-                graph[s][i] -= min_cap;
-                graph[i][s] += min_cap;
-                return Some(min_cap);
+    /// The amount of players and who can see whom
+    fn players(input: &str) -> Vec<Vec<bool>> {
+        let mut numbers = input.split_whitespace().map(|x| x.parse::<usize>().unwrap());
+        let (n, m) = (numbers.next().unwrap(), numbers.next().unwrap());
+        let mut sees = vec![vec![false; n]; n];
+        for _ in 0..m {
+            let (a, b) = (numbers.next().unwrap() - 1, numbers.next().unwrap() - 1);
+            sees[a][b] = true;
+            sees[b][a] = true;
+        }
+        sees
+    }
+
+    /// Assigns the players one by one to every target which was not hit yet
+    fn assign(sees: &[Vec<bool>], targets: &mut Vec<usize>, hit: &mut Vec<bool>) -> bool {
+        let i = targets.len();
+        if i == sees.len() {
+            return true;
+        }
+        for o in 0..sees.len() {
+            if sees[i][o] && !hit[o] {
+                hit[o] = true;
+                targets.push(o);
+                if assign(sees, targets, hit) {
+                    return true;
+                }
+                targets.pop();
+                hit[o] = false;
             }
         }
+        false
     }
 
-    None
-}
+    fn brute(mut input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
+        let mut s = String::new();
+        input.read_to_string(&mut s)?;
+        let sees = players(&s);
+
+        let mut targets = Vec::new();
+        if assign(&sees, &mut targets, &mut vec![false; sees.len()]) {
+            for target in targets {
+                writeln!(&mut w, "{}", target + 1)?;
+            }
+        } else {
+            writeln!(&mut w, "Impossible")?;
+        }
+        Ok(())
+    }
 
-fn min_flow(graph: &mut Vec<Vec<i16>>, s: usize, t: usize) -> i16 {
-    let mut flow = 0;
+    /// Any assignment is accepted, as long as everyone shoots a player they see and everyone is hit once
+    fn compare(input: &str, output: &str, expected: &str) -> Result<(), String> {
+        if expected.trim() == "Impossible" || output.trim() == "Impossible" {
+            return crate::judge::Checker::Exact.check(output, expected);
+        }
 
-    while let Some(pushed_flow) = dfs(graph, &mut vec![false; graph.len()], s, t, std::i16::MAX) {
-        flow += pushed_flow;
+        let sees = players(input);
+        let targets: Vec<&str> = output.lines().collect();
+        if targets.len() != sees.len() {
+            return Err(format!("expected {} targets, got {}", sees.len(), targets.len()));
+        }
+        let mut hit = vec![false; sees.len()];
+        for (i, target) in targets.iter().enumerate() {
+            match target.parse::<usize>() {
+                Ok(o) if (1..=sees.len()).contains(&o) && sees[i][o - 1] && !hit[o - 1] => hit[o - 1] = true,
+                _ => return Err(format!("player {} cannot shoot '{}'", i + 1, target)),
+            }
+        }
+        Ok(())
     }
 
-    flow
-}
+    /// Few players with a random set of pairs who see each other, often without a valid assignment
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let n = rng.range(2..=size + 1);
+        let pairs: Vec<(usize, usize)> =
+            (1..=n).flat_map(|a| (a + 1..=n).map(move |b| (a, b))).filter(|_| rng.chance(0.4)).collect();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let mut input = format!("{} {}\n", n, pairs.len());
+        for (a, b) in pairs {
+            input.push_str(&format!("{} {}\n", a, b));
+        }
+        input
+    }
 
     #[test]
-    fn paintball_sampleinputs() {
-        for mut file in std::fs::read_dir("input/paintball")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert_eq!(
-                std::str::from_utf8(&output_writer).unwrap().trim(),
-                std::fs::read_to_string(&file).unwrap().trim(),
-                "file: {:?}",
-                file
-            );
-        }
+    fn paintball_stress() {
+        crate::judge::stress_with("paintball", generate, |input, output| solve(input, output), |input, output| brute(input, output), compare);
     }
 }
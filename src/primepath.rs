@@ -1,79 +1,35 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
-use std::io::{self, BufRead};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
 
-pub fn main() {
-    let stdin = io::stdin();
-    let mut lines = stdin.lock().lines();
-
-    let graph: HashMap<u16, Vec<u16>> = (1000..=9999).filter(|&n| is_prime(n)).map(|n| (n, neighbours(n))).collect();
-
-    for _ in 0..(lines.next().unwrap().unwrap().parse::<u16>().unwrap()) {
-        let line = lines.next().unwrap().unwrap();
-        let mut ns = line.split(' ');
-        let res = dijkstra(ns.next().unwrap().parse().unwrap(), ns.next().unwrap().parse().unwrap(), &graph);
-
-        match res {
-            Some(res) => println!("{}", res),
-            None => println!("Impossible")
-        }
-    }
+use crate::snippets::graph::dijkstra;
 
+/// main entry point of program when invoked directly in the terminal
+#[allow(dead_code)]
+fn main() -> Result<(), Box<dyn Error>> {
+    solve(io::stdin().lock(), io::stdout().lock())
 }
 
-fn dijkstra(from: u16, to: u16, graph: &HashMap<u16, Vec<u16>>) -> Option<u16> {
-    // Dijkstra
-    #[derive(Eq, PartialEq)]
-    struct Edge {
-        cost: u16,
-        vertex: u16
-    }
-
-    impl Ord for Edge {
-        fn cmp(&self, other: &Self) -> Ordering {
-            other.cost.cmp(&self.cost).then_with(|| other.vertex.cmp(&self.vertex))
-        }
-    }
-
-    impl PartialOrd for Edge {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-            Some(self.cmp(other))
-        }
-    }
-
-    let mut dist: HashMap<u16, u16> = graph.keys().map(|&x| (x, std::u16::MAX)).collect();
-    dist.insert(from, 0);
+fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
+    let mut lines = input.lines();
 
-    let mut prev: HashMap<u16, Option<u16>> = graph.keys().map(|&x| (x, None)).collect();
+    // Every number is a vertex, only the four digit primes have edges
+    let graph: Vec<Vec<(usize, u16)>> = (0..=9999)
+        .map(|n| if n >= 1000 && is_prime(n) { neighbours(n).into_iter().map(|m| (m as usize, 1)).collect() } else { vec![] })
+        .collect();
 
-    let mut heap: BinaryHeap<Edge> = BinaryHeap::new();
-    heap.push(Edge {
-        cost: 0,
-        vertex: from
-    });
-
-    while let Some(Edge { cost, vertex }) = heap.pop() {
-        if vertex == to {
-            return Some(cost);
-        }
-
-        let v_dist = *dist.get(&vertex).unwrap();
-
-        if cost > v_dist { continue; }
+    for _ in 0..(lines.next().unwrap().unwrap().parse::<u16>().unwrap()) {
+        let line = lines.next().unwrap().unwrap();
+        let mut ns = line.split(' ');
+        let from: usize = ns.next().unwrap().parse().unwrap();
+        let to: usize = ns.next().unwrap().parse().unwrap();
 
-        for neighbour in graph.get(&vertex).unwrap() {
-            if v_dist + 1 < *dist.get(neighbour).unwrap() {
-                dist.insert(*neighbour, v_dist + 1);
-                prev.insert(*neighbour, Some(vertex));
-                heap.push(Edge {
-                    cost: v_dist + 1,
-                    vertex: *neighbour
-                });
-            }
+        match dijkstra(&graph, &[(from, 0)], Some(to)).dist[to] {
+            Some(res) => writeln!(&mut w, "{}", res).unwrap(),
+            None => writeln!(&mut w, "Impossible").unwrap()
         }
     }
 
-    None
+    Ok(())
 }
 
 fn neighbours(from: u16) -> Vec<u16> {
@@ -99,4 +55,12 @@ fn is_prime(n: u16) -> bool {
         }
     }
     true
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn primepath_sampleinputs() {
+        crate::judge::test_samples("primepath", |input, output| super::solve(input, output));
+    }
+}
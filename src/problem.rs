@@ -0,0 +1,204 @@
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::bundle::bundle;
+use crate::metadata::Metadata;
+
+/// Root of the repository, all paths of a problem are relative to this directory
+pub(crate) fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// A single Kattis problem, consisting of a solution in `src/<name>.rs` and samples in `input/<name>/`. Some older
+/// problems have a single sample stored as `input/<name>.in` instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Problem {
+    name: String,
+}
+
+/// A sample input with the answer the judge expects, if we have one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Sample {
+    pub name: String,
+    pub input: PathBuf,
+    pub answer: Option<PathBuf>,
+}
+
+impl Problem {
+    /// Kattis problem ids only contain lowercase letters and digits, but we also accept dashes and underscores.
+    /// Dashes are mapped to underscores so the name can be used as a module name.
+    pub fn new(id: &str) -> Result<Self, Box<dyn Error>> {
+        let name = id.trim().to_lowercase().replace('-', "_");
+
+        if name.is_empty() {
+            return Err("no problem name given".into());
+        }
+
+        if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("invalid problem name '{}', only letters, digits, dashes and underscores are allowed", id).into());
+        }
+
+        if name.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(format!("invalid problem name '{}', it can not be used as a module name because it starts with a digit", id).into());
+        }
+
+        Ok(Problem { name })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn source(&self) -> PathBuf {
+        root().join("src").join(format!("{}.rs", self.name))
+    }
+
+    pub fn input_dir(&self) -> PathBuf {
+        root().join("input").join(&self.name)
+    }
+
+    /// Location of the binary compiled the same way Kattis compiles it
+    pub fn binary(&self) -> PathBuf {
+        root().join("target").join("problems").join(&self.name)
+    }
+
+    /// Location of the bundled source, the file which should be submitted to Kattis
+    pub fn bundled(&self) -> PathBuf {
+        self.binary().with_extension("rs")
+    }
+
+    /// Bundles the solution with the snippets it uses, see [`crate::bundle`], and returns the bundled source
+    pub fn bundle(&self) -> Result<String, Box<dyn Error>> {
+        let source = self.source();
+        if !source.is_file() {
+            return Err(format!("{} does not exist, create it with `kattis new {}`", source.display(), self.name).into());
+        }
+
+        bundle(&fs::read_to_string(&source)?)
+    }
+
+    /// Location of the binary compiled with overflow checks, see [`Problem::compile_checked`]
+    pub fn checked_binary(&self) -> PathBuf {
+        root().join("target").join("problems").join(format!("{}-checked", self.name))
+    }
+
+    /// Compiles the bundled solution with the same flags Kattis uses, returns the path to the binary
+    pub fn compile(&self) -> Result<PathBuf, Box<dyn Error>> {
+        self.rustc(self.binary(), &[])
+    }
+
+    /// Compiles the bundled solution like [`Problem::compile`], but arithmetic overflow panics instead of wrapping
+    pub fn compile_checked(&self) -> Result<PathBuf, Box<dyn Error>> {
+        self.rustc(self.checked_binary(), &["-C", "overflow-checks=on"])
+    }
+
+    fn rustc(&self, binary: PathBuf, flags: &[&str]) -> Result<PathBuf, Box<dyn Error>> {
+        let bundled = self.bundled();
+        fs::create_dir_all(binary.parent().unwrap())?;
+        fs::write(&bundled, self.bundle()?)?;
+
+        let status = Command::new("rustc")
+            .args(["-O", "--crate-type", "bin", "--edition=2018"])
+            .args(flags)
+            .arg(&bundled)
+            .arg("-o")
+            .arg(&binary)
+            .status()
+            .map_err(|e| format!("could not start rustc: {}", e))?;
+
+        if !status.success() {
+            return Err(format!("compilation of {} failed", bundled.display()).into());
+        }
+
+        Ok(binary)
+    }
+
+    /// Location of the metadata file, see [`Metadata`]
+    pub fn metadata_path(&self) -> PathBuf {
+        self.input_dir().join("problem.toml")
+    }
+
+    /// The metadata in `input/<name>/problem.toml`, or the defaults when the problem has none
+    pub fn metadata(&self) -> Result<Metadata, Box<dyn Error>> {
+        let path = self.metadata_path();
+        if !path.is_file() {
+            return Ok(Metadata::default());
+        }
+
+        Metadata::parse(&fs::read_to_string(&path)?).map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    /// Location of a sample stored in the older flat layout, `input/<name>.in`
+    pub fn flat_input(&self) -> PathBuf {
+        root().join("input").join(format!("{}.in", self.name))
+    }
+
+    /// All `*.in` files in `input/<name>/` and the flat `input/<name>.in`, paired with their `*.ans` file and sorted
+    /// by name
+    pub fn samples(&self) -> io::Result<Vec<Sample>> {
+        let dir = self.input_dir();
+        let mut inputs = Vec::new();
+        if dir.is_dir() {
+            for entry in fs::read_dir(&dir)? {
+                inputs.push(entry?.path());
+            }
+        }
+        if self.flat_input().is_file() {
+            inputs.push(self.flat_input());
+        }
+
+        let mut samples = Vec::new();
+        for input in inputs {
+            if input.extension() != Some(OsStr::new("in")) {
+                continue;
+            }
+
+            let answer = input.with_extension("ans");
+            samples.push(Sample {
+                name: input.file_stem().unwrap().to_string_lossy().into_owned(),
+                answer: if answer.is_file() { Some(answer) } else { None },
+                input,
+            });
+        }
+
+        samples.sort_by(|a, b| natural_cmp(&a.name, &b.name));
+        Ok(samples)
+    }
+
+    /// Inputs written by `kattis gen`, in their own directory so they are not taken for samples
+    pub fn generated_dir(&self) -> PathBuf {
+        self.input_dir().join("gen")
+    }
+
+    /// All `*.in` files in `input/<name>/gen/`, which have no answer, sorted by name
+    pub fn generated(&self) -> io::Result<Vec<Sample>> {
+        let dir = self.generated_dir();
+        let mut generated = Vec::new();
+        if dir.is_dir() {
+            for entry in fs::read_dir(&dir)? {
+                let input = entry?.path();
+                if input.extension() == Some(OsStr::new("in")) {
+                    generated.push(Sample { name: format!("gen/{}", input.file_stem().unwrap().to_string_lossy()), input, answer: None });
+                }
+            }
+        }
+
+        generated.sort_by(|a, b| natural_cmp(&a.name, &b.name));
+        Ok(generated)
+    }
+}
+
+/// Compares names such that `2` is sorted before `10`
+fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    let key = |s: &str| {
+        let digits = s.trim_start_matches(|c: char| !c.is_ascii_digit());
+        let prefix = &s[..s.len() - digits.len()];
+        let digits = &digits[..digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len())];
+        (prefix.to_string(), digits.trim_start_matches('0').len(), digits.trim_start_matches('0').to_string(), s.to_string())
+    };
+    key(a).cmp(&key(b))
+}
@@ -143,25 +143,8 @@ fn graham(points: &mut Vec<Point>) -> Vec<&Point> {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn roberthood_sampleinputs() {
-        for mut file in std::fs::read_dir("input/roberthood")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert!(
-                (std::str::from_utf8(&output_writer).unwrap().trim().parse::<f64>().unwrap() - std::fs::read_to_string(&file).unwrap().trim().parse::<f64>().unwrap()).abs() < 10.0f64.powi(-6)
-            );
-        }
+        crate::judge::test_samples("roberthood", |input, output| super::solve(input, output));
     }
 }
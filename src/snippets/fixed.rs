@@ -0,0 +1,246 @@
+//! Exact decimal numbers: `value / 10^scale` in an `i128`, so inputs like `-12.345` or `1e2` are computed with
+//! integers. Operations panic when the result does not fit, also in release builds, the scale of a product is the sum
+//! of the scales.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Fixed {
+    pub value: i128,
+    pub scale: u32,
+}
+
+fn pow10(exp: u32) -> Option<i128> {
+    10i128.checked_pow(exp)
+}
+
+impl Fixed {
+    pub fn new(value: i128, scale: u32) -> Self {
+        Fixed { value, scale }
+    }
+
+    /// The same number with the given scale, `None` if digits would be lost or the value does not fit
+    pub fn rescale(self, scale: u32) -> Option<Self> {
+        if scale >= self.scale {
+            Some(Fixed { value: self.value.checked_mul(pow10(scale - self.scale)?)?, scale })
+        } else {
+            let divisor = pow10(self.scale - scale)?;
+            if self.value % divisor == 0 {
+                Some(Fixed { value: self.value / divisor, scale })
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Rounds to the given scale, halves are rounded away from zero
+    pub fn round(self, scale: u32) -> Self {
+        if scale >= self.scale {
+            return self.rescale(scale).expect("overflow while rescaling");
+        }
+        let divisor = pow10(self.scale - scale).expect("overflow while rounding");
+        let (quotient, remainder) = (self.value / divisor, self.value % divisor);
+        let (remainder, divisor) = (remainder.unsigned_abs(), divisor.unsigned_abs());
+        let value = if remainder >= divisor - remainder { quotient + self.value.signum() } else { quotient };
+        Fixed { value, scale }
+    }
+
+    /// Both values with the larger of the two scales
+    fn align(self, other: Self) -> (i128, i128, u32) {
+        let scale = self.scale.max(other.scale);
+        let rescale = |x: Fixed| x.rescale(scale).expect("overflow while aligning scales").value;
+        (rescale(self), rescale(other), scale)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.value as f64 / 10f64.powi(self.scale as i32)
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+
+    fn add(self, other: Fixed) -> Fixed {
+        let (a, b, scale) = self.align(other);
+        Fixed { value: a.checked_add(b).expect("overflow while adding"), scale }
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+
+    fn sub(self, other: Fixed) -> Fixed {
+        let (a, b, scale) = self.align(other);
+        Fixed { value: a.checked_sub(b).expect("overflow while subtracting"), scale }
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+
+    fn mul(self, other: Fixed) -> Fixed {
+        let value = self.value.checked_mul(other.value).expect("overflow while multiplying");
+        Fixed { value, scale: self.scale.checked_add(other.scale).expect("overflow while multiplying") }
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+
+    fn neg(self) -> Fixed {
+        Fixed { value: self.value.checked_neg().expect("overflow while negating"), scale: self.scale }
+    }
+}
+
+/// Numbers are equal when their values are, whatever their scale: `1.5 == 1.50`
+impl PartialEq for Fixed {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Fixed {}
+
+impl PartialOrd for Fixed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The number with fewer decimals is scaled up to compare the values, when that overflows it is further from zero
+impl Ord for Fixed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b, flipped) = if self.scale <= other.scale { (self, other, false) } else { (other, self, true) };
+        let ordering = if a.value == 0 {
+            0.cmp(&b.value)
+        } else {
+            match pow10(b.scale - a.scale).and_then(|factor| a.value.checked_mul(factor)) {
+                Some(value) => value.cmp(&b.value),
+                None => a.value.cmp(&0),
+            }
+        };
+        if flipped { ordering.reverse() } else { ordering }
+    }
+}
+
+/// Parses decimals like `-12.345`, `+3`, `.5` and `1.5e-3` exactly, the scale is the amount of decimals
+impl FromStr for Fixed {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("'{}' is not a decimal number", s);
+        let (mantissa, exp) = match s.find(['e', 'E']) {
+            Some(i) => (&s[..i], s[i + 1..].parse::<i32>().map_err(|_| invalid())?),
+            None => (s, 0),
+        };
+        let unsigned = mantissa.strip_prefix(['-', '+']).unwrap_or(mantissa);
+        let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let digits = |d: &str| d.bytes().all(|c| c.is_ascii_digit());
+        if int.len() + frac.len() == 0 || !digits(int) || !digits(frac) {
+            return Err(invalid());
+        }
+
+        let out_of_range = || format!("'{}' is out of range", s);
+        let mut value: i128 = 0;
+        for c in int.bytes().chain(frac.bytes()) {
+            value = value.checked_mul(10).and_then(|v| v.checked_add((c - b'0') as i128)).ok_or_else(out_of_range)?;
+        }
+        if mantissa.starts_with('-') {
+            value = -value;
+        }
+
+        // Scales beyond 10^38 do not fit in an i128, the value could not even be printed
+        let scale = frac.len() as i64 - exp as i64;
+        let factor = pow10(scale.unsigned_abs().min(u32::MAX as u64) as u32).ok_or_else(out_of_range)?;
+        if scale >= 0 {
+            Ok(Fixed { value, scale: scale as u32 })
+        } else {
+            Ok(Fixed { value: value.checked_mul(factor).ok_or_else(out_of_range)?, scale: 0 })
+        }
+    }
+}
+
+/// Prints all decimals of the scale, or rounds to the precision if one is given: `format!("{:.2}", x)`
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let x = match f.precision() {
+            Some(decimals) => self.round(decimals as u32),
+            None => *self,
+        };
+        let divisor = pow10(x.scale).expect("overflow while formatting").unsigned_abs();
+        let (int, frac) = (x.value.unsigned_abs() / divisor, x.value.unsigned_abs() % divisor);
+
+        let sign = if x.value < 0 { "-" } else { "" };
+        if x.scale == 0 {
+            write!(f, "{}{}", sign, int)
+        } else {
+            write!(f, "{}{}.{:0width$}", sign, int, frac, width = x.scale as usize)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixed(s: &str) -> Fixed {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!((fixed("-12.345").value, fixed("-12.345").scale), (-12345, 3));
+        assert_eq!((fixed("+3").value, fixed("+3").scale), (3, 0));
+        assert_eq!((fixed("1e2").value, fixed("1e2").scale), (100, 0));
+        assert_eq!((fixed("1.5E-3").value, fixed("1.5E-3").scale), (15, 4));
+        assert_eq!(fixed(".5"), fixed("0.50"));
+        assert_eq!(fixed("7."), Fixed::new(7, 0));
+        for s in &["", ".", "-", "1.2.3", "1e", "e5", "1,5", "--1", "0x10", "1e99", "1e-99"] {
+            assert!(s.parse::<Fixed>().is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(fixed("0.1") + fixed("0.2"), fixed("0.3"));
+        assert_eq!(fixed("1") - fixed("0.001"), fixed("0.999"));
+        assert_eq!(fixed("1.5") * fixed("-0.25"), fixed("-0.375"));
+        assert_eq!((fixed("1.5") * fixed("-0.25")).scale, 3);
+        assert_eq!(-fixed("2.5"), fixed("-2.50"));
+        assert!(fixed("-0.001") < fixed("0") && fixed("2") > fixed("1.999"));
+        assert!(fixed("1e30") > fixed("1e-30") && fixed("-1e30") < fixed("1e-30") && fixed("-1e-30") > fixed("-1e30"));
+        assert_eq!(Fixed::new(0, 100), fixed("0"));
+        assert!(Fixed::new(-1, 100) < fixed("0") && fixed("0") < Fixed::new(1, 100));
+
+        assert_eq!(fixed("1.230").rescale(2), Some(Fixed::new(123, 2)));
+        assert_eq!(fixed("1.235").rescale(2), None);
+        assert_eq!(fixed("1.5").rescale(3).map(|x| x.value), Some(1500));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow while multiplying")]
+    fn overflow() {
+        let _ = fixed("1e20") * fixed("1e20");
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(fixed("-12.340").to_string(), "-12.340");
+        assert_eq!(fixed("1e3").to_string(), "1000");
+        assert_eq!(format!("{:.2}", fixed("2.345")), "2.35");
+        assert_eq!(format!("{:.2}", fixed("-2.345")), "-2.35");
+        assert_eq!(format!("{:.1}", fixed("-0.04")), "0.0");
+        assert_eq!(format!("{:.3}", fixed("0.5")), "0.500");
+        assert_eq!(format!("{:.0}", fixed("2.5")), "3");
+        assert_eq!(format!("{:.0}", Fixed::new(i128::MIN, 38)), "-2");
+        assert_eq!(format!("{:.0}", Fixed::new(-9 * 10i128.pow(37), 38)), "-1");
+        assert_eq!(format!("{:.0}", Fixed::new(5 * 10i128.pow(37), 38)), "1");
+        assert_eq!(format!("{:.0}", Fixed::new(5 * 10i128.pow(37) - 1, 38)), "0");
+        assert_eq!(Fixed::new(i128::MIN, 0).to_string(), "-170141183460469231731687303715884105728");
+        assert_eq!(Fixed::new(i128::MIN, 38).to_string(), "-1.70141183460469231731687303715884105728");
+        assert_eq!(fixed("0.25").to_f64(), 0.25);
+    }
+}
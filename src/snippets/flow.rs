@@ -0,0 +1,181 @@
+//! Maximum flow with Dinic on an edge list: `O(V^2 E)` in general, `O(E sqrt(V))` for unit capacity matchings.
+//! Capacities can be any ordered type whose `Default` is zero, like the unsigned integers.
+
+use std::collections::VecDeque;
+use std::ops::{Add, Sub};
+
+/// An edge of a [`FlowGraph`], every edge has a reverse edge holding the flow which can be pushed back
+#[derive(Debug, Clone)]
+pub struct Edge<C> {
+    pub from: usize,
+    pub to: usize,
+    pub cap: C,
+    residual: C,
+    undirected: bool,
+}
+
+impl<C: Copy + Ord + Default + Sub<Output = C>> Edge<C> {
+    /// The flow through the edge, zero when the flow of an undirected edge goes the other way
+    pub fn flow(&self) -> C {
+        if self.residual < self.cap { self.cap - self.residual } else { C::default() }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FlowGraph<C> {
+    /// Edge `i ^ 1` is the reverse of edge `i`
+    edges: Vec<Edge<C>>,
+    adj: Vec<Vec<usize>>,
+    level: Vec<usize>,
+    next: Vec<usize>,
+}
+
+impl<C> FlowGraph<C>
+where
+    C: Copy + Ord + Default + Add<Output = C> + Sub<Output = C>,
+{
+    pub fn new(n: usize) -> Self {
+        FlowGraph { edges: Vec::new(), adj: vec![Vec::new(); n], level: vec![0; n], next: vec![0; n] }
+    }
+
+    fn push_pair(&mut self, from: usize, to: usize, cap: C, reverse_cap: C, undirected: bool) -> usize {
+        let id = self.edges.len();
+        self.edges.push(Edge { from, to, cap, residual: cap, undirected });
+        self.edges.push(Edge { from: to, to: from, cap: reverse_cap, residual: reverse_cap, undirected });
+        self.adj[from].push(id);
+        self.adj[to].push(id + 1);
+        id
+    }
+
+    /// Adds an edge `from -> to` and returns its id
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: C) -> usize {
+        self.push_pair(from, to, cap, C::default(), false)
+    }
+
+    /// Adds an edge with the capacity in both directions and returns its id
+    pub fn add_undirected_edge(&mut self, a: usize, b: usize, cap: C) -> usize {
+        self.push_pair(a, b, cap, cap, true)
+    }
+
+    /// Raises the capacity of an edge, in both directions for undirected edges. The current flow stays valid, so
+    /// [`FlowGraph::max_flow`] continues from it.
+    pub fn raise_capacity(&mut self, id: usize, amount: C) {
+        let directions = if self.edges[id].undirected { 2 } else { 1 };
+        for &e in [id, id ^ 1].iter().take(directions) {
+            self.edges[e].cap = self.edges[e].cap + amount;
+            self.edges[e].residual = self.edges[e].residual + amount;
+        }
+    }
+
+    pub fn edge(&self, id: usize) -> &Edge<C> {
+        &self.edges[id]
+    }
+
+    /// The edges leaving `v`, including the reverse edges which have no capacity of their own
+    pub fn edges_from(&self, v: usize) -> impl Iterator<Item = &Edge<C>> + '_ {
+        self.adj[v].iter().map(move |&id| &self.edges[id])
+    }
+
+    /// Pushes as much extra flow from `s` to `t` as possible and returns the amount, on top of any earlier flow
+    pub fn max_flow(&mut self, s: usize, t: usize) -> C {
+        assert_ne!(s, t, "the source is the sink");
+        let mut total = C::default();
+        while self.bfs(s, t) {
+            self.next.iter_mut().for_each(|next| *next = 0);
+            loop {
+                let pushed = self.dfs(s, t, None);
+                if pushed == C::default() {
+                    break;
+                }
+                total = total + pushed;
+            }
+        }
+        total
+    }
+
+    /// Levels of the vertices in the residual graph, whether `t` can still be reached
+    fn bfs(&mut self, s: usize, t: usize) -> bool {
+        self.level.iter_mut().for_each(|level| *level = usize::MAX);
+        self.level[s] = 0;
+        let mut queue = VecDeque::new();
+        queue.push_back(s);
+
+        while let Some(v) = queue.pop_front() {
+            for &id in &self.adj[v] {
+                let edge = &self.edges[id];
+                if edge.residual > C::default() && self.level[edge.to] == usize::MAX {
+                    self.level[edge.to] = self.level[v] + 1;
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+        self.level[t] != usize::MAX
+    }
+
+    /// Pushes flow along one path of the level graph, `limit` is `None` at the source. Edges which lead nowhere are
+    /// skipped for the rest of the phase.
+    fn dfs(&mut self, v: usize, t: usize, limit: Option<C>) -> C {
+        if v == t {
+            return limit.unwrap();
+        }
+
+        while self.next[v] < self.adj[v].len() {
+            let id = self.adj[v][self.next[v]];
+            let (to, residual) = (self.edges[id].to, self.edges[id].residual);
+            if residual > C::default() && self.level[to] == self.level[v] + 1 {
+                let limit = match limit {
+                    Some(limit) if limit < residual => limit,
+                    _ => residual,
+                };
+                let pushed = self.dfs(to, t, Some(limit));
+                if pushed > C::default() {
+                    self.edges[id].residual = self.edges[id].residual - pushed;
+                    self.edges[id ^ 1].residual = self.edges[id ^ 1].residual + pushed;
+                    return pushed;
+                }
+            }
+            self.next[v] += 1;
+        }
+        C::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_flow() {
+        // Two paths joined by a cross edge, the edges leaving the source are the minimum cut
+        let mut graph: FlowGraph<u32> = FlowGraph::new(4);
+        let a = graph.add_edge(0, 1, 2);
+        graph.add_edge(0, 2, 4);
+        let cross = graph.add_edge(2, 1, 10);
+        graph.add_edge(1, 3, 5);
+        let b = graph.add_edge(2, 3, 3);
+        assert_eq!(graph.max_flow(0, 3), 6);
+        assert_eq!(graph.max_flow(0, 3), 0);
+        assert_eq!((graph.edge(a).flow(), graph.edge(cross).flow(), graph.edge(b).flow()), (2, 1, 3));
+        assert_eq!(graph.edges_from(0).map(|e| e.flow()).sum::<u32>(), 6);
+
+        // Continues from the current flow
+        graph.raise_capacity(a, 1);
+        assert_eq!(graph.max_flow(0, 3), 1);
+        graph.raise_capacity(b, 5);
+        assert_eq!(graph.max_flow(0, 3), 0);
+        assert_eq!(graph.edges_from(0).map(|e| e.flow()).sum::<u32>(), 7);
+    }
+
+    #[test]
+    fn undirected() {
+        let mut graph: FlowGraph<u64> = FlowGraph::new(3);
+        let ab = graph.add_undirected_edge(0, 1, 3);
+        let bc = graph.add_undirected_edge(2, 1, 1);
+        assert_eq!(graph.max_flow(0, 2), 1);
+        assert_eq!((graph.edge(ab).flow(), graph.edge(bc).flow(), graph.edge(bc ^ 1).flow()), (1, 0, 1));
+
+        graph.raise_capacity(bc, 4);
+        assert_eq!(graph.max_flow(0, 2), 2);
+        assert_eq!(graph.max_flow(2, 0), 6);
+    }
+}
@@ -0,0 +1,107 @@
+//! Shortest paths in graphs stored as adjacency lists: `graph[v]` holds a `(u, cost)` pair for every edge `v -> u`,
+//! undirected graphs have both directions
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::Add;
+
+/// Result of [`dijkstra`], vertices which were not reached (or not settled before the target) have no distance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPaths<C> {
+    pub dist: Vec<Option<C>>,
+    /// The vertex before every vertex on a shortest path, `None` for the sources
+    pub prev: Vec<Option<usize>>,
+    /// Amount of shortest paths from any source, only correct when all costs are positive
+    pub count: Vec<u64>,
+}
+
+impl<C> ShortestPaths<C> {
+    /// The vertices of a shortest path from a source to `target`
+    pub fn path(&self, target: usize) -> Option<Vec<usize>> {
+        self.dist[target].as_ref()?;
+        let mut path = vec![target];
+        while let Some(v) = self.prev[*path.last().unwrap()] {
+            path.push(v);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Dijkstra from every source at the given initial cost, stopping as soon as `target` is settled. The costs can be
+/// of any ordered type whose zero is the initial cost of the sources, like integers or `Fixed`, but not negative.
+pub fn dijkstra<C>(graph: &[Vec<(usize, C)>], sources: &[(usize, C)], target: Option<usize>) -> ShortestPaths<C>
+where
+    C: Copy + Ord + Add<Output = C>,
+{
+    let n = graph.len();
+    let mut res = ShortestPaths { dist: vec![None; n], prev: vec![None; n], count: vec![0; n] };
+    let mut settled = vec![false; n];
+    let mut heap = BinaryHeap::new();
+
+    for &(source, cost) in sources {
+        match res.dist[source] {
+            Some(d) if d <= cost => {}
+            _ => {
+                res.dist[source] = Some(cost);
+                res.count[source] = 1;
+                heap.push(Reverse((cost, source)));
+            }
+        }
+    }
+
+    while let Some(Reverse((cost, v))) = heap.pop() {
+        if settled[v] {
+            continue;
+        }
+        settled[v] = true;
+        if target == Some(v) {
+            break;
+        }
+
+        for &(u, edge) in &graph[v] {
+            let next = cost + edge;
+            match res.dist[u] {
+                Some(d) if next > d => {}
+                Some(d) if next == d => {
+                    if !settled[u] {
+                        res.count[u] += res.count[v];
+                    }
+                }
+                _ => {
+                    res.dist[u] = Some(next);
+                    res.prev[u] = Some(v);
+                    res.count[u] = res.count[v];
+                    heap.push(Reverse((next, u)));
+                }
+            }
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortest_paths() {
+        // 0 -> 1 -> 3 and 0 -> 2 -> 3 both cost 3, 4 is unreachable
+        let graph: Vec<Vec<(usize, u32)>> = vec![vec![(1, 1), (2, 2)], vec![(3, 2)], vec![(3, 1)], vec![(0, 1)], vec![]];
+        let paths = dijkstra(&graph, &[(0, 0)], None);
+        assert_eq!(paths.dist, vec![Some(0), Some(1), Some(2), Some(3), None]);
+        assert_eq!(paths.count, vec![1, 1, 1, 2, 0]);
+        assert_eq!(paths.path(3), Some(vec![0, 1, 3]));
+        assert_eq!(paths.path(4), None);
+
+        let early = dijkstra(&graph, &[(0, 0)], Some(1));
+        assert_eq!(early.dist[1], Some(1));
+        assert_eq!(early.path(1), Some(vec![0, 1]));
+
+        // The second source starts with a cost, like a flight taken earlier
+        let multi = dijkstra(&graph, &[(1, 5), (2, 0)], None);
+        assert_eq!(multi.dist, vec![Some(2), Some(3), Some(0), Some(1), None]);
+        assert_eq!(multi.path(1), Some(vec![2, 3, 0, 1]));
+    }
+}
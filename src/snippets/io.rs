@@ -0,0 +1,172 @@
+//! Fast input reading: a scanner of whitespace separated tokens, so extra spaces and `\r\n` line endings do not matter
+
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// A token which could not be read, `line` and `column` are 1-based and the column counts bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ScanError {
+    fn new(line: usize, column: usize, message: String) -> Self {
+        ScanError { line, column, message }
+    }
+}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ScanError {}
+
+/// Reads whitespace separated tokens byte by byte from the buffer of the reader, without allocating per line
+pub struct Scanner<R> {
+    reader: R,
+    token: Vec<u8>,
+    /// Position of the next byte
+    line: usize,
+    column: usize,
+}
+
+impl<R: BufRead> Scanner<R> {
+    pub fn new(reader: R) -> Self {
+        Scanner { reader, token: Vec::new(), line: 1, column: 1 }
+    }
+
+    /// Skips whitespace, stops at the first byte of the next token or at the end of the input
+    fn skip_whitespace(&mut self) -> Result<(), ScanError> {
+        loop {
+            let buf = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(e) => return Err(ScanError::new(self.line, self.column, e.to_string())),
+            };
+            if buf.is_empty() {
+                return Ok(());
+            }
+
+            let whitespace = buf.iter().take_while(|c| c.is_ascii_whitespace()).count();
+            for &c in &buf[..whitespace] {
+                if c == b'\n' {
+                    self.line += 1;
+                    self.column = 1;
+                } else {
+                    self.column += 1;
+                }
+            }
+            let done = whitespace < buf.len();
+            self.reader.consume(whitespace);
+            if done {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Whether only whitespace is left, for inputs which end without a terminating line
+    pub fn at_end(&mut self) -> bool {
+        self.skip_whitespace().is_ok() && matches!(self.reader.fill_buf(), Ok(buf) if buf.is_empty())
+    }
+
+    /// Reads the next token and parses it
+    pub fn token<T: FromStr>(&mut self) -> Result<T, ScanError> {
+        self.skip_whitespace()?;
+        let (line, column) = (self.line, self.column);
+
+        self.token.clear();
+        loop {
+            let buf = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(e) => return Err(ScanError::new(line, column, e.to_string())),
+            };
+            let len = buf.iter().take_while(|c| !c.is_ascii_whitespace()).count();
+            self.token.extend_from_slice(&buf[..len]);
+            let done = len < buf.len() || buf.is_empty();
+            self.reader.consume(len);
+            if done {
+                break;
+            }
+        }
+        self.column += self.token.len();
+
+        if self.token.is_empty() {
+            return Err(ScanError::new(line, column, "expected a token, got the end of the input".to_string()));
+        }
+        let text = std::str::from_utf8(&self.token).map_err(|_| ScanError::new(line, column, "the token is not valid UTF-8".to_string()))?;
+        text.parse()
+            .map_err(|_| ScanError::new(line, column, format!("'{}' is not a valid {}", text, std::any::type_name::<T>())))
+    }
+
+    /// Reads a tuple of tokens, like `let (n, m): (usize, u64) = scanner.tuple()?;`
+    pub fn tuple<T: Tuple>(&mut self) -> Result<T, ScanError> {
+        T::scan(self)
+    }
+
+    /// Reads the next `n` tokens
+    pub fn vec<T: FromStr>(&mut self, n: usize) -> Result<Vec<T>, ScanError> {
+        (0..n).map(|_| self.token()).collect()
+    }
+}
+
+/// Tuples of two to four tokens, read by [`Scanner::tuple`]
+pub trait Tuple: Sized {
+    fn scan<R: BufRead>(scanner: &mut Scanner<R>) -> Result<Self, ScanError>;
+}
+
+impl<A: FromStr, B: FromStr> Tuple for (A, B) {
+    fn scan<R: BufRead>(scanner: &mut Scanner<R>) -> Result<Self, ScanError> {
+        Ok((scanner.token()?, scanner.token()?))
+    }
+}
+
+impl<A: FromStr, B: FromStr, C: FromStr> Tuple for (A, B, C) {
+    fn scan<R: BufRead>(scanner: &mut Scanner<R>) -> Result<Self, ScanError> {
+        Ok((scanner.token()?, scanner.token()?, scanner.token()?))
+    }
+}
+
+impl<A: FromStr, B: FromStr, C: FromStr, D: FromStr> Tuple for (A, B, C, D) {
+    fn scan<R: BufRead>(scanner: &mut Scanner<R>) -> Result<Self, ScanError> {
+        Ok((scanner.token()?, scanner.token()?, scanner.token()?, scanner.token()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens() {
+        let mut scanner = Scanner::new("3  -4\r\n\r\n1.5 abc\n 7 8 9 10\n".as_bytes());
+        assert_eq!(scanner.tuple::<(usize, i64)>(), Ok((3, -4)));
+        assert_eq!(scanner.token::<f64>(), Ok(1.5));
+        assert_eq!(scanner.token::<String>(), Ok("abc".to_string()));
+        assert_eq!(scanner.vec::<u8>(3), Ok(vec![7, 8, 9]));
+        assert!(!scanner.at_end());
+        assert_eq!(scanner.token::<u32>(), Ok(10));
+        assert!(scanner.at_end());
+
+        let error = |line, column, message: &str| ScanError { line, column, message: message.to_string() };
+        assert_eq!(scanner.token::<u32>(), Err(error(5, 1, "expected a token, got the end of the input")));
+        let mut scanner = Scanner::new("1\n  x2 3".as_bytes());
+        assert_eq!(scanner.tuple::<(u8, u8, u8)>(), Err(error(2, 3, "'x2' is not a valid u8")));
+        assert_eq!(scanner.token::<i8>(), Ok(3));
+        assert_eq!(Scanner::new(" -1".as_bytes()).token::<u64>().unwrap_err().to_string(), "line 1, column 2: '-1' is not a valid u64");
+    }
+
+    #[test]
+    fn small_buffers() {
+        // Tokens and line breaks crossing the end of the buffer of the reader
+        let input: String = (0..1000).map(|i| format!("{}{}", i, if i % 7 == 0 { "\r\n" } else { " " })).collect();
+        let mut scanner = Scanner::new(std::io::BufReader::with_capacity(3, input.as_bytes()));
+        assert_eq!(scanner.vec::<u32>(1000), Ok((0..1000).collect()));
+        assert!(scanner.at_end());
+        assert_eq!(scanner.line, 144);
+    }
+}
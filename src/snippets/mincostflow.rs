@@ -0,0 +1,208 @@
+//! Minimum cost flow with successive shortest paths: Bellman-Ford once for the potentials, so edge costs can be
+//! negative, and then Dijkstra on the reduced costs for every augmenting path. Negative cycles are not supported.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// An edge of a [`MinCostFlow`], the reverse edge has no capacity and the negated cost
+#[derive(Debug, Clone)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub cap: i64,
+    pub cost: i64,
+    residual: i64,
+}
+
+impl Edge {
+    /// The flow through the edge, zero for reverse edges
+    pub fn flow(&self) -> i64 {
+        (self.cap - self.residual).max(0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MinCostFlow {
+    /// Edge `i ^ 1` is the reverse of edge `i`
+    edges: Vec<Edge>,
+    adj: Vec<Vec<usize>>,
+}
+
+impl MinCostFlow {
+    pub fn new(n: usize) -> Self {
+        MinCostFlow { edges: Vec::new(), adj: vec![Vec::new(); n] }
+    }
+
+    /// Adds an edge `from -> to` where every unit of flow costs `cost`, and returns its id
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: i64, cost: i64) -> usize {
+        let id = self.edges.len();
+        self.edges.push(Edge { from, to, cap, cost, residual: cap });
+        self.edges.push(Edge { from: to, to: from, cap: 0, cost: -cost, residual: 0 });
+        self.adj[from].push(id);
+        self.adj[to].push(id + 1);
+        id
+    }
+
+    pub fn edge(&self, id: usize) -> &Edge {
+        &self.edges[id]
+    }
+
+    /// The edges leaving `v`, including the reverse edges which have no capacity of their own
+    pub fn edges_from(&self, v: usize) -> impl Iterator<Item = &Edge> + '_ {
+        self.adj[v].iter().map(move |&id| &self.edges[id])
+    }
+
+    /// Pushes the maximum flow from `s` to `t`, or at most `limit`, at the minimum cost. Returns the extra flow and
+    /// its cost, on top of any earlier flow.
+    pub fn min_cost_flow(&mut self, s: usize, t: usize, limit: Option<i64>) -> (i64, i64) {
+        assert_ne!(s, t, "the source is the sink");
+        assert!(limit.unwrap_or(0) >= 0, "the limit is negative");
+        let n = self.adj.len();
+        let mut potential = self.bellman_ford(s);
+        let (mut flow, mut cost) = (0, 0);
+
+        while limit != Some(flow) {
+            // Dijkstra on the reduced costs, which are never negative for edges with residual capacity
+            let mut dist: Vec<Option<i64>> = vec![None; n];
+            let mut prev: Vec<Option<usize>> = vec![None; n];
+            let mut heap = BinaryHeap::new();
+            dist[s] = Some(0);
+            heap.push(Reverse((0, s)));
+
+            while let Some(Reverse((d, v))) = heap.pop() {
+                if dist[v] != Some(d) {
+                    continue;
+                }
+                for &id in &self.adj[v] {
+                    let edge = &self.edges[id];
+                    if edge.residual == 0 {
+                        continue;
+                    }
+                    let next = d + edge.cost + potential[v].unwrap() - potential[edge.to].unwrap();
+                    match dist[edge.to] {
+                        Some(current) if current <= next => {}
+                        _ => {
+                            dist[edge.to] = Some(next);
+                            prev[edge.to] = Some(id);
+                            heap.push(Reverse((next, edge.to)));
+                        }
+                    }
+                }
+            }
+
+            if dist[t].is_none() {
+                break;
+            }
+            for (potential, dist) in potential.iter_mut().zip(&dist) {
+                if let (Some(p), Some(d)) = (potential.as_mut(), dist) {
+                    *p += d;
+                }
+            }
+
+            // The bottleneck of the path, then push it along the path
+            let mut pushed = limit.map_or(i64::MAX, |limit| limit - flow);
+            let mut v = t;
+            while let Some(id) = prev[v] {
+                pushed = pushed.min(self.edges[id].residual);
+                v = self.edges[id].from;
+            }
+            let mut v = t;
+            while let Some(id) = prev[v] {
+                self.edges[id].residual -= pushed;
+                self.edges[id ^ 1].residual += pushed;
+                cost += pushed * self.edges[id].cost;
+                v = self.edges[id].from;
+            }
+            flow += pushed;
+        }
+
+        (flow, cost)
+    }
+
+    /// Shortest distances from `s` over the edges with residual capacity, `None` for unreachable vertices. Those stay
+    /// unreachable, since flow is only pushed between reachable vertices.
+    fn bellman_ford(&self, s: usize) -> Vec<Option<i64>> {
+        let n = self.adj.len();
+        let mut dist: Vec<Option<i64>> = vec![None; n];
+        dist[s] = Some(0);
+
+        for round in 0..n {
+            let mut changed = false;
+            for edge in self.edges.iter().filter(|edge| edge.residual > 0) {
+                match (dist[edge.from], dist[edge.to]) {
+                    (Some(d), Some(current)) if current <= d + edge.cost => {}
+                    (Some(d), _) => {
+                        dist[edge.to] = Some(d + edge.cost);
+                        changed = true;
+                    }
+                    (None, _) => {}
+                }
+            }
+            if !changed {
+                return dist;
+            }
+            assert!(round + 1 < n, "the graph has a negative cycle");
+        }
+        dist
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Workers `0..3` and jobs `3..6` with the cost of every assignment, source 6 and sink 7
+    fn assignment(costs: &[[i64; 3]; 3]) -> (MinCostFlow, Vec<Vec<usize>>) {
+        let mut graph = MinCostFlow::new(8);
+        let mut ids = vec![vec![0; 3]; 3];
+        for i in 0..3 {
+            graph.add_edge(6, i, 1, 0);
+            graph.add_edge(3 + i, 7, 1, 0);
+            for j in 0..3 {
+                ids[i][j] = graph.add_edge(i, 3 + j, 1, costs[i][j]);
+            }
+        }
+        (graph, ids)
+    }
+
+    #[test]
+    fn assignments() {
+        // Greedily taking the cheapest assignment (1, 0) costs 1 + 6 + 4, the optimum is 2 + 3 + 4
+        let costs = [[2, 6, 9], [1, 3, 8], [5, 7, 4]];
+        let (mut graph, ids) = assignment(&costs);
+        assert_eq!(graph.min_cost_flow(6, 7, None), (3, 9));
+        let flows: Vec<Vec<i64>> = ids.iter().map(|row| row.iter().map(|&id| graph.edge(id).flow()).collect()).collect();
+        assert_eq!(flows, vec![vec![1, 0, 0], vec![0, 1, 0], vec![0, 0, 1]]);
+        assert_eq!(graph.edges_from(6).map(|e| e.flow()).sum::<i64>(), 3);
+        assert_eq!(graph.min_cost_flow(6, 7, None), (0, 0));
+
+        // Maximum profit with negated costs, and a limited flow
+        let (mut graph, _) = assignment(&[[-2, -6, -9], [-1, -3, -8], [-5, -7, -4]]);
+        assert_eq!(graph.min_cost_flow(6, 7, None), (3, -6 - 8 - 5));
+        let (mut graph, _) = assignment(&costs);
+        assert_eq!(graph.min_cost_flow(6, 7, Some(2)), (2, 5));
+        assert_eq!(graph.min_cost_flow(6, 7, Some(5)), (1, 4));
+    }
+
+    #[test]
+    #[should_panic(expected = "the limit is negative")]
+    fn negative_limit() {
+        let (mut graph, _) = assignment(&[[1; 3]; 3]);
+        graph.min_cost_flow(6, 7, Some(-1));
+    }
+
+    #[test]
+    fn negative_costs() {
+        // The negative edge makes the long path 0 -> 1 -> 2 -> 3 cheaper than 0 -> 3, vertex 4 is unreachable
+        let mut graph = MinCostFlow::new(5);
+        let direct = graph.add_edge(0, 3, 2, 1);
+        graph.add_edge(0, 1, 1, 2);
+        let negative = graph.add_edge(1, 2, 1, -4);
+        graph.add_edge(2, 3, 5, 1);
+        graph.add_edge(4, 3, 1, -10);
+        assert_eq!(graph.min_cost_flow(0, 3, Some(1)), (1, -1));
+        assert_eq!((graph.edge(negative).flow(), graph.edge(direct).flow()), (1, 0));
+        assert_eq!(graph.min_cost_flow(0, 3, None), (2, 2));
+        assert_eq!(graph.edge(direct).flow(), 2);
+    }
+}
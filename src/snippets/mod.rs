@@ -1,2 +1,7 @@
-/// These helper function are not used directly but are copied to the problem itself.
-pub(crate) mod geometry;
\ No newline at end of file
+/// Helpers shared between solutions, `kattis bundle` inlines the used parts into the submitted file.
+pub(crate) mod fixed;
+pub(crate) mod flow;
+pub(crate) mod geometry;
+pub(crate) mod graph;
+pub(crate) mod io;
+pub(crate) mod mincostflow;
\ No newline at end of file
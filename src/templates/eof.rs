@@ -0,0 +1,30 @@
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+/// main entry point of program when invoked directly in the terminal
+#[allow(dead_code)]
+fn main() -> Result<(), Box<dyn Error>> {
+    solve(io::stdin().lock(), io::stdout().lock())
+}
+
+fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
+    for line in input.lines().map(|x| x.unwrap()) {
+        // Some problems end the input with a line containing a single 0 instead
+        if line.trim() == "0" {
+            break;
+        }
+        let mut numbers = line.split_whitespace().map(|x| x.parse::<i64>().unwrap());
+
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[ignore = "a template, its test runs once `kattis new` made it a problem"]
+    fn default_sampleinputs() {
+        crate::judge::test_samples("default", |input, output| super::solve(input, output));
+    }
+}
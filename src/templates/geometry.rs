@@ -0,0 +1,35 @@
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+use crate::snippets::geometry::*;
+
+/// main entry point of program when invoked directly in the terminal
+#[allow(dead_code)]
+fn main() -> Result<(), Box<dyn Error>> {
+    solve(io::stdin().lock(), io::stdout().lock())
+}
+
+fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
+    let mut lines = input.lines().map(|x| x.unwrap());
+    let n: usize = lines.next().unwrap().trim().parse()?;
+
+    let points: Vec<Point<f64>> = (&mut lines)
+        .take(n)
+        .map(|l| {
+            let mut coordinates = l.split_whitespace().map(|x| x.parse::<f64>().unwrap());
+            Point { x: coordinates.next().unwrap(), y: coordinates.next().unwrap() }
+        })
+        .collect();
+
+    writeln!(w, "{:.6}", 0.0)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[ignore = "a template, its test runs once `kattis new` made it a problem"]
+    fn default_sampleinputs() {
+        crate::judge::test_samples("default", |input, output| super::solve(input, output));
+    }
+}
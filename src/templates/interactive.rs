@@ -0,0 +1,56 @@
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+/// main entry point of program when invoked directly in the terminal
+#[allow(dead_code)]
+fn main() -> Result<(), Box<dyn Error>> {
+    solve(io::stdin().lock(), io::stdout().lock())
+}
+
+fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
+    let mut lines = input.lines();
+    let n: usize = lines.next().ok_or("the judge sent nothing")??.trim().parse()?;
+
+    for query in 0..n {
+        // The judge only answers once the query has been flushed
+        writeln!(w, "? {}", query)?;
+        w.flush()?;
+        let answer = lines.next().ok_or("the judge stopped answering")??;
+    }
+
+    writeln!(w, "! ")?;
+    w.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::judge::{Channel, Judge, Verdict};
+
+    /// Plays the role of the Kattis interactor for a single test case
+    struct Interactor {
+        n: usize,
+    }
+
+    impl Judge for Interactor {
+        fn interact(&mut self, io: &mut Channel) -> Result<(), Verdict> {
+            io.write_line(self.n);
+            for _ in 0..self.n {
+                let query = io.read_line()?;
+                io.write_line(0);
+            }
+
+            let answer = io.read_line()?;
+            if answer != "! " {
+                return Err(Verdict::WrongAnswer(format!("wrong answer '{}'", answer)));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    #[ignore = "a template, its test runs once `kattis new` made it a problem"]
+    fn default_interactive() {
+        crate::judge::test_interactive("default", vec![("1".to_string(), Interactor { n: 1 })]);
+    }
+}
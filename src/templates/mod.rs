@@ -0,0 +1,10 @@
+//! Templates of `kattis new --template`, `src/default.rs` is the `batch` template. Like `default.rs` every template
+//! names its problem `default`, which is replaced by the name of the new problem.
+//!
+//! The templates and their tests are compiled with the helper so they keep up with the snippets and the judge, but the
+//! tests are ignored until `kattis new` made the template a problem: they would judge the samples in `input/default/`
+//! instead of their own.
+mod eof;
+mod geometry;
+mod interactive;
+mod multi;
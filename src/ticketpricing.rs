@@ -1,7 +1,9 @@
-use std::collections::BinaryHeap;
-use std::cmp::{self, Ordering};
+use std::cmp;
 use std::error::Error;
 use std::io::{self, BufRead, Write};
+use std::ops::Add;
+
+use crate::snippets::graph::dijkstra;
 
 /// main entry point of program when invoked directly in the terminal
 #[allow(dead_code)]
@@ -35,119 +37,51 @@ fn solve(input: impl BufRead, mut writer: impl Write) -> Result<(), Box<dyn Erro
 }
 
 
-fn longest_path(ori_weeks_left: usize, ori_tickets_left: usize, week_prices: &Vec<Vec<(usize, usize)>>) -> (isize, usize) {
-
-    #[derive(Debug, PartialEq, Eq)]
-    struct Vertex {
-        weeks_left: usize,
-        tickets_left: usize,
-        total_income: isize
-    }
+/// Revenue after some weeks. Weeks are compared first, so Dijkstra settles the weeks in order like a longest path in a
+/// DAG, then the highest revenue and the lowest price in the first week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+struct Revenue {
+    weeks: usize,
+    loss: isize,
+    first_price: usize
+}
 
-    impl Ord for Vertex {
-        fn cmp(&self, other: &Self) -> Ordering { 
-            self.weeks_left.cmp(&other.weeks_left).then_with(|| self.total_income.cmp(&other.total_income).then_with(|| self.tickets_left.cmp(&other.tickets_left)))
-        }
-    }
+impl Add for Revenue {
+    type Output = Revenue;
 
-    impl PartialOrd for Vertex {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> { 
-            Some(self.cmp(other))
-         }
+    fn add(self, other: Revenue) -> Revenue {
+        Revenue { weeks: self.weeks + other.weeks, loss: self.loss + other.loss, first_price: self.first_price + other.first_price }
     }
+}
 
-    // We use a heap to keep track of discovered vertexes since not all will be discovered
-    let mut heap: BinaryHeap<Vertex> = BinaryHeap::new();
-    heap.push(Vertex {
-        weeks_left: ori_weeks_left + 1,
-        tickets_left: ori_tickets_left,
-        total_income: 0
-    });
-
-    // Maybe change this to a HashMap if Vec is too big, especially since it's very sparse
-    let mut dist = vec![vec![-std::isize::MAX; ori_tickets_left + 1]; ori_weeks_left + 2]; 
-    dist[ori_weeks_left + 1][ori_tickets_left] = 0;
-
-    let mut origin = vec![vec![std::usize::MAX; ori_tickets_left + 1]; ori_weeks_left + 1];
-
-    while let Some(Vertex { weeks_left, tickets_left, total_income }) = heap.pop() {
-        if weeks_left == 0 {
-            return (total_income, origin[0][tickets_left]); // The first week -1 we encounter should contain the highest total_income;
-        }
-
-        if dist[weeks_left][tickets_left] > total_income {
-            continue; // We have already found a longer path to this node
-        }
-
-        // If 0 tickets are left we follow an 'extra' edge to the final week.
-        if tickets_left == 0 && weeks_left != ori_weeks_left + 1 {
-            if dist[weeks_left][0] > dist[(weeks_left - 1)][0] {
-                dist[weeks_left - 1][0] = dist[weeks_left][0];
-                origin[weeks_left - 1][0] = origin[weeks_left][tickets_left];
-                heap.push(Vertex {
-                    weeks_left: weeks_left - 1,
-                    tickets_left: 0,
-                    total_income: dist[(weeks_left - 1)][0]
-                });
-            } else if dist[weeks_left][0] == dist[(weeks_left - 1)][0] {
-                origin[weeks_left - 1][0] = cmp::min(origin[weeks_left][tickets_left], origin[weeks_left - 1][0]);
-            }
-            continue;
-        }
-
-        for &(price, tickets_sold_for_price) in week_prices[weeks_left - 1].iter() {
-            let tickets_sold_for_price = cmp::min(tickets_sold_for_price, tickets_left);
-            if dist[weeks_left - 1][(tickets_left - tickets_sold_for_price)] < dist[weeks_left][tickets_left] + (tickets_sold_for_price * price) as isize {
-                dist[weeks_left - 1][(tickets_left - tickets_sold_for_price)] = dist[weeks_left][tickets_left] + (tickets_sold_for_price * price) as isize;
-
-                if weeks_left == ori_weeks_left + 1 {
-                    origin[weeks_left - 1][tickets_left - tickets_sold_for_price] = price;
-                } else {
-                    origin[weeks_left - 1][tickets_left - tickets_sold_for_price] = origin[weeks_left][tickets_left];
-                }
-
-                heap.push(Vertex {
-                    weeks_left: weeks_left - 1,
-                    tickets_left: tickets_left - tickets_sold_for_price,
-                    total_income: dist[weeks_left - 1][tickets_left - tickets_sold_for_price]
-                });
-            } else if dist[weeks_left - 1][tickets_left - tickets_sold_for_price] == dist[weeks_left][tickets_left] + (tickets_sold_for_price * price) as isize {
-                if weeks_left == ori_weeks_left + 1 {
-                    origin[weeks_left - 1][tickets_left - tickets_sold_for_price] = cmp::min(origin[weeks_left - 1][tickets_left - tickets_sold_for_price], price);
-                } else {
-                    origin[weeks_left - 1][tickets_left - tickets_sold_for_price] = cmp::min(origin[weeks_left - 1][tickets_left - tickets_sold_for_price], origin[weeks_left][tickets_left]);
-                }
+fn longest_path(ori_weeks_left: usize, ori_tickets_left: usize, week_prices: &[Vec<(usize, usize)>]) -> (isize, usize) {
+    // Vertex `week * (tickets + 1) + tickets_left` for every week and the end, and a sink after the end
+    let (weeks, tickets) = (ori_weeks_left + 1, ori_tickets_left);
+    let vertex = |week: usize, tickets_left: usize| week * (tickets + 1) + tickets_left;
+    let sink = vertex(weeks + 1, 0);
+
+    let mut graph: Vec<Vec<(usize, Revenue)>> = vec![Vec::new(); sink + 1];
+    for week in 0..weeks {
+        for tickets_left in 0..=tickets {
+            for &(price, tickets_sold_for_price) in week_prices[weeks - 1 - week].iter() {
+                let sold = cmp::min(tickets_sold_for_price, tickets_left);
+                let revenue = Revenue { weeks: 1, loss: -((sold * price) as isize), first_price: if week == 0 { price } else { 0 } };
+                graph[vertex(week, tickets_left)].push((vertex(week + 1, tickets_left - sold), revenue));
             }
         }
-    };
+    }
+    for tickets_left in 0..=tickets {
+        graph[vertex(weeks, tickets_left)].push((sink, Revenue::default()));
+    }
 
-    unreachable!();
+    let best = dijkstra(&graph, &[(vertex(0, tickets), Revenue::default())], Some(sink)).dist[sink].unwrap();
+    (-best.loss, best.first_price)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn ticketpricing_sampleinputs() {
-        for mut file in std::fs::read_dir("input/ticketpricing")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert_eq!(
-                std::str::from_utf8(&output_writer).unwrap().trim(),
-                std::fs::read_to_string(&file).unwrap().trim(),
-                "file: {:?}",
-                file
-            );
-        }
+        crate::judge::test_samples("ticketpricing", |input, output| super::solve(input, output));
     }
 }
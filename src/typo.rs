@@ -93,27 +93,48 @@ fn check_word(mut hash: i64, word: &str, list: &HashMap<i64, (String, usize)>) -
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::judge::Rng;
+    use std::collections::HashSet;
 
     #[test]
     fn typo_sampleinputs() {
-        for mut file in std::fs::read_dir("input/typo")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert_eq!(
-                std::str::from_utf8(&output_writer).unwrap().trim(),
-                std::fs::read_to_string(&file).unwrap().trim(),
-                "file: {:?}",
-                &file
-            );
+        crate::judge::test_samples("typo", |input, output| super::solve(input, output));
+    }
+
+    /// Tries every deletion of every word, without any hashing
+    fn brute(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
+        let words: Vec<String> = input.lines().skip(1).map(|x| x.unwrap()).collect();
+        let dictionary: HashSet<&str> = words.iter().map(String::as_str).collect();
+
+        let typos: Vec<&String> = words
+            .iter()
+            .filter(|word| (0..word.len()).any(|i| dictionary.contains(&format!("{}{}", &word[..i], &word[i + 1..])[..])))
+            .collect();
+
+        for word in &typos {
+            writeln!(&mut w, "{}", word)?;
+        }
+        if typos.is_empty() {
+            writeln!(&mut w, "NO TYPOS")?;
         }
+        Ok(())
+    }
+
+    /// Distinct words over a tiny alphabet, so deleting a letter often gives another word
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut seen = HashSet::new();
+        let mut words = Vec::new();
+        for _ in 0..size * 3 {
+            let word: String = (0..rng.range(1..=size.min(8))).map(|_| *rng.choose(&['a', 'b', 'c'])).collect();
+            if seen.insert(word.clone()) {
+                words.push(word);
+            }
+        }
+        format!("{}\n{}\n", words.len(), words.join("\n"))
+    }
+
+    #[test]
+    fn typo_stress() {
+        crate::judge::stress("typo", generate, |input, output| solve(input, output), |input, output| brute(input, output));
     }
 }
@@ -1,7 +1,9 @@
 use std::error::Error;
 use std::io::{self, BufRead, Write};
-use std::collections::VecDeque;
-use std::cmp;
+use std::collections::HashMap;
+
+use crate::snippets::flow::FlowGraph;
+use crate::snippets::io::Scanner;
 
 /// main entry point of program when invoked directly in the terminal
 #[allow(dead_code)]
@@ -10,31 +12,32 @@ fn main() -> Result<(), Box<dyn Error>> {
 }
 
 fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
-    let mut lines = input.lines().map(|x| x.unwrap()).map(|x| {
-        let mut line_iter = x.split(' ');
-        (line_iter.next().unwrap().parse::<usize>().unwrap(), line_iter.next().unwrap().parse::<usize>().unwrap(), line_iter.next().unwrap().parse::<usize>().unwrap())
-    });
-
-    let (n, p, k) = lines.next().unwrap();
+    let mut scanner = Scanner::new(input);
+    let (n, p, k): (usize, usize, usize) = scanner.tuple()?;
 
-    let mut res_graph: Vec<Vec<isize>> = vec![vec![0; n]; n];
+    let mut graph: FlowGraph<usize> = FlowGraph::new(n);
+    let mut pipes: HashMap<(usize, usize), usize> = HashMap::new();
 
     for _ in 0..p {
-        let (a, b, c) = lines.next().unwrap();
-        res_graph[a - 1][b - 1] = c as isize;
-        res_graph[b - 1][a - 1] = c as isize;
+        let (a, b, c): (usize, usize, usize) = scanner.tuple()?;
+        pipes.insert((a.min(b), a.max(b)), graph.add_undirected_edge(a - 1, b - 1, c));
     }
 
-    let mut total = min_flow(&mut res_graph, 0, 1);
+    let mut total = graph.max_flow(0, 1);
     writeln!(&mut w, "{}", total).unwrap();
 
 
+    // Improving a pipe keeps the current flow valid, only the extra flow has to be found
     for _ in 0..k {
-        let (a, b, c) = lines.next().unwrap();
-        res_graph[a - 1][b - 1] += c as isize;
-        res_graph[b - 1][a - 1] += c as isize;
+        let (a, b, c): (usize, usize, usize) = scanner.tuple()?;
+        match pipes.get(&(a.min(b), a.max(b))) {
+            Some(&pipe) => graph.raise_capacity(pipe, c),
+            None => {
+                pipes.insert((a.min(b), a.max(b)), graph.add_undirected_edge(a - 1, b - 1, c));
+            }
+        }
 
-        total += min_flow(&mut res_graph, 0, 1);
+        total += graph.max_flow(0, 1);
         writeln!(&mut w, "{}", total).unwrap();
     }
 
@@ -42,78 +45,40 @@ fn solve(input: impl BufRead, mut w: impl Write) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn bfs(graph: &mut Vec<Vec<isize>>, s: usize, mut t: usize) -> Option<isize> {
-    let mut queue: VecDeque<usize> = VecDeque::new();
-    queue.push_back(s);
-
-    let mut prev: Vec<Option<(usize, isize)>> = vec![None; graph.len()];
-    prev[s] = Some((s, std::isize::MAX));
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
 
-    while let Some(v) = queue.pop_front() {
-        for u in 0..graph.len() {
-            if graph[v][u] == 0 || prev[u].is_some() {
-                continue;
-            }
+    use crate::judge::{Input, ValidationError};
 
-            if prev[u].is_some() {
-                continue;
-            }
+    #[test]
+    fn water_sampleinputs() {
+        crate::judge::test_samples("water", |input, output| super::solve(input, output));
+    }
 
-            prev[u] = Some((v, cmp::min(prev[v].unwrap().1, graph[v][u])));
-            queue.push_back(u);
-
-            if u == t {
-                let mut from = t;
-                let min_cap = prev[t].unwrap().1;
-                while t != s {
-                    t = prev[t].unwrap().0;
-                    graph[t][from] -= min_cap;
-                    graph[from][t] += min_cap;
-                    from = t;
-                }
-                
-                return Some(min_cap);
+    /// The initial pipes are distinct, the improvements can also add a new pipe
+    fn validate(input: &str) -> Result<(), ValidationError> {
+        let mut input = Input::new(input);
+        let mut line = input.line(3)?;
+        let n = line.int(2..=100usize)?;
+        let p = line.int(0..=n * (n - 1) / 2)?;
+        let k = line.int(1..=10_000usize)?;
+
+        let mut pipes = HashSet::new();
+        for i in 0..p + k {
+            let mut line = input.line(3)?;
+            let (a, b) = (line.int(1..=n)?, line.int(1..=n)?);
+            line.int(1..=1000usize)?;
+            input.check(a != b, || format!("station {} is connected to itself", a))?;
+            if i < p {
+                input.check(pipes.insert((a.min(b), a.max(b))), || format!("stations {} and {} are connected twice", a, b))?;
             }
         }
+        input.eof()
     }
 
-    None
-}
-
-fn min_flow(graph: &mut Vec<Vec<isize>>, s: usize, t: usize) -> isize {
-    let mut flow = 0;
-
-    while let Some(pushed_flow) = bfs(graph, s, t) {
-        flow += pushed_flow;
-    }
-
-    flow
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
     #[test]
-    fn water_sampleinputs() {
-        for mut file in std::fs::read_dir("input/water")
-            .unwrap()
-            .filter(|f| f.is_ok() && f.as_ref().unwrap().path().extension().unwrap() == "in")
-            .map(|f| f.unwrap().path())
-        {
-            let mut output_writer: Vec<u8> = Vec::new();
-            solve(
-                std::fs::read_to_string(&file).unwrap().as_bytes(),
-                &mut output_writer,
-            )
-            .unwrap();
-            file.set_extension("ans");
-            assert_eq!(
-                std::str::from_utf8(&output_writer).unwrap().trim(),
-                std::fs::read_to_string(&file).unwrap().trim(),
-                "file: {:?}",
-                file
-            );
-        }
+    fn water_validate() {
+        crate::judge::validate("water", validate);
     }
 }
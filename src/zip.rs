@@ -0,0 +1,366 @@
+//! Minimal ZIP reader for the `samples.zip` files of Kattis, supporting stored and deflated entries.
+//!
+//! Only what these archives use is implemented: a single disk without ZIP64 or encryption. Deflate follows RFC 1951.
+use std::convert::TryInto;
+
+/// A file in the archive, directories are left out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Entry {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Result<usize, String> {
+    match data.get(offset..offset + 2) {
+        Some(b) => Ok(u16::from_le_bytes(b.try_into().unwrap()) as usize),
+        None => Err("unexpected end of the archive".to_string()),
+    }
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Result<usize, String> {
+    match data.get(offset..offset + 4) {
+        Some(b) => Ok(u32::from_le_bytes(b.try_into().unwrap()) as usize),
+        None => Err("unexpected end of the archive".to_string()),
+    }
+}
+
+const LOCAL_HEADER: usize = 0x0403_4b50;
+const CENTRAL_HEADER: usize = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY: usize = 0x0605_4b50;
+
+/// Reads all files of the archive, the data is checked against the CRC-32 stored in the archive
+pub(crate) fn read(zip: &[u8]) -> Result<Vec<Entry>, String> {
+    // The end of central directory record is at the end, followed by a comment of at most 65535 bytes
+    let end = (zip.len().saturating_sub(22 + 0xffff)..=zip.len().saturating_sub(22))
+        .rev()
+        .find(|&i| u32_at(zip, i) == Ok(END_OF_CENTRAL_DIRECTORY))
+        .ok_or("not a zip file")?;
+
+    let count = u16_at(zip, end + 10)?;
+    let mut offset = u32_at(zip, end + 16)?;
+    if count == 0xffff || offset == 0xffff_ffff {
+        return Err("ZIP64 archives are not supported".to_string());
+    }
+
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        if u32_at(zip, offset)? != CENTRAL_HEADER {
+            return Err("corrupt central directory".to_string());
+        }
+
+        let flags = u16_at(zip, offset + 8)?;
+        let method = u16_at(zip, offset + 10)?;
+        let crc = u32_at(zip, offset + 16)?;
+        let compressed = u32_at(zip, offset + 20)?;
+        let size = u32_at(zip, offset + 24)?;
+        let name_len = u16_at(zip, offset + 28)?;
+        let header = u32_at(zip, offset + 42)?;
+        let name = zip.get(offset + 46..offset + 46 + name_len).ok_or("unexpected end of the archive")?;
+        let name = String::from_utf8_lossy(name).into_owned();
+        offset += 46 + name_len + u16_at(zip, offset + 30)? + u16_at(zip, offset + 32)?;
+
+        if name.ends_with('/') {
+            continue;
+        }
+        if flags & 1 != 0 {
+            return Err(format!("{} is encrypted", name));
+        }
+
+        // The sizes in the local header can be missing, so only its variable length fields are used
+        if u32_at(zip, header)? != LOCAL_HEADER {
+            return Err(format!("corrupt local header of {}", name));
+        }
+        let start = header + 30 + u16_at(zip, header + 26)? + u16_at(zip, header + 28)?;
+        let raw = zip.get(start..start + compressed).ok_or_else(|| format!("unexpected end of the archive in {}", name))?;
+
+        let data = match method {
+            0 => raw.to_vec(),
+            8 => inflate(raw).map_err(|e| format!("{}: {}", name, e))?,
+            _ => return Err(format!("{} uses the unsupported compression method {}", name, method)),
+        };
+        if data.len() != size || crc32(&data) as usize != crc {
+            return Err(format!("{} is corrupt, its size or checksum does not match", name));
+        }
+
+        entries.push(Entry { name, data });
+    }
+
+    Ok(entries)
+}
+
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in data {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Reads the deflate stream bit by bit, starting with the least significant bit of every byte
+struct Bits<'a> {
+    data: &'a [u8],
+    pos: usize,
+    buffer: u32,
+    count: u32,
+}
+
+impl Bits<'_> {
+    fn bits(&mut self, n: u32) -> Result<usize, String> {
+        while self.count < n {
+            let byte = *self.data.get(self.pos).ok_or("unexpected end of the compressed data")?;
+            self.buffer |= (byte as u32) << self.count;
+            self.pos += 1;
+            self.count += 8;
+        }
+        let bits = self.buffer & ((1u32 << n) - 1);
+        self.buffer >>= n;
+        self.count -= n;
+        Ok(bits as usize)
+    }
+}
+
+/// Canonical Huffman code, `counts[l]` is the amount of codes of length `l` and `symbols` is ordered by code
+struct Huffman {
+    counts: [usize; 16],
+    symbols: Vec<usize>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Self, String> {
+        let mut counts = [0; 16];
+        for &l in lengths {
+            counts[l as usize] += 1;
+        }
+
+        // Over-subscribed codes can not be decoded, incomplete ones are allowed
+        let mut left = 1isize;
+        for &count in &counts[1..] {
+            left = left * 2 - count as isize;
+            if left < 0 {
+                return Err("invalid Huffman code".to_string());
+            }
+        }
+
+        let mut offsets = [0; 16];
+        for l in 1..15 {
+            offsets[l + 1] = offsets[l] + counts[l];
+        }
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, &l) in lengths.iter().enumerate() {
+            if l != 0 {
+                symbols[offsets[l as usize]] = symbol;
+                offsets[l as usize] += 1;
+            }
+        }
+
+        counts[0] = 0;
+        Ok(Huffman { counts, symbols })
+    }
+
+    fn decode(&self, bits: &mut Bits) -> Result<usize, String> {
+        let (mut code, mut first, mut index) = (0, 0, 0);
+        for l in 1..16 {
+            code |= bits.bits(1)?;
+            let count = self.counts[l];
+            if code < first + count {
+                return Ok(self.symbols[index + code - first]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err("invalid Huffman code in the compressed data".to_string())
+    }
+}
+
+const LENGTH_BASE: [usize; 29] = [3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258];
+const LENGTH_EXTRA: [u32; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+const DISTANCE_BASE: [usize; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u32; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+/// Order in which the lengths of the code length code are stored
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// Decompresses a raw deflate stream
+pub(crate) fn inflate(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut bits = Bits { data, pos: 0, buffer: 0, count: 0 };
+    let mut out = Vec::new();
+
+    loop {
+        let last = bits.bits(1)? == 1;
+        match bits.bits(2)? {
+            0 => {
+                // Stored blocks start at a byte boundary
+                bits.buffer = 0;
+                bits.count = 0;
+                let len = u16_at(data, bits.pos)?;
+                if u16_at(data, bits.pos + 2)? != !len & 0xffff {
+                    return Err("corrupt stored block".to_string());
+                }
+                let block = data.get(bits.pos + 4..bits.pos + 4 + len).ok_or("unexpected end of the compressed data")?;
+                out.extend_from_slice(block);
+                bits.pos += 4 + len;
+            }
+            1 => {
+                let mut lengths = [8; 288];
+                lengths[144..256].iter_mut().for_each(|l| *l = 9);
+                lengths[256..280].iter_mut().for_each(|l| *l = 7);
+                inflate_block(&mut bits, &mut out, &Huffman::new(&lengths)?, &Huffman::new(&[5; 30])?)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(&mut bits)?;
+                inflate_block(&mut bits, &mut out, &literals, &distances)?;
+            }
+            _ => return Err("invalid block type".to_string()),
+        }
+
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+/// Reads the literal/length and distance codes at the start of a block with dynamic Huffman codes
+fn dynamic_codes(bits: &mut Bits) -> Result<(Huffman, Huffman), String> {
+    let literals = bits.bits(5)? + 257;
+    let distances = bits.bits(5)? + 1;
+    let code_lengths = bits.bits(4)? + 4;
+
+    let mut lengths = [0; 19];
+    for &i in &CODE_LENGTH_ORDER[..code_lengths] {
+        lengths[i] = bits.bits(3)? as u8;
+    }
+    let code = Huffman::new(&lengths)?;
+
+    let mut lengths = Vec::with_capacity(literals + distances);
+    while lengths.len() < literals + distances {
+        let (length, repeat) = match code.decode(bits)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => (*lengths.last().ok_or("repeated length without a previous length")?, 3 + bits.bits(2)?),
+            17 => (0, 3 + bits.bits(3)?),
+            _ => (0, 11 + bits.bits(7)?),
+        };
+        if lengths.len() + repeat > literals + distances {
+            return Err("too many code lengths".to_string());
+        }
+        lengths.extend(std::iter::repeat_n(length, repeat));
+    }
+
+    if lengths[256] == 0 {
+        return Err("the block has no end code".to_string());
+    }
+    Ok((Huffman::new(&lengths[..literals])?, Huffman::new(&lengths[literals..])?))
+}
+
+fn inflate_block(bits: &mut Bits, out: &mut Vec<u8>, literals: &Huffman, distances: &Huffman) -> Result<(), String> {
+    loop {
+        match literals.decode(bits)? {
+            symbol @ 0..=255 => out.push(symbol as u8),
+            256 => return Ok(()),
+            symbol => {
+                let symbol = symbol - 257;
+                if symbol >= LENGTH_BASE.len() {
+                    return Err("invalid length code".to_string());
+                }
+                let length = LENGTH_BASE[symbol] + bits.bits(LENGTH_EXTRA[symbol])?;
+
+                let symbol = distances.decode(bits)?;
+                if symbol >= DISTANCE_BASE.len() {
+                    return Err("invalid distance code".to_string());
+                }
+                let distance = DISTANCE_BASE[symbol] + bits.bits(DISTANCE_EXTRA[symbol])?;
+                if distance > out.len() {
+                    return Err("distance is too far back".to_string());
+                }
+
+                // The copy can overlap with itself, so it is done byte by byte
+                let start = out.len() - distance;
+                for i in 0..length {
+                    out.push(out[start + i]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Archive with the given names, compression methods, compressed data and uncompressed data
+    fn archive(entries: &[(&str, u16, &[u8], &[u8])]) -> Vec<u8> {
+        let (mut zip, mut central) = (Vec::new(), Vec::new());
+        for &(name, method, raw, data) in entries {
+            let mut fields = Vec::new();
+            for n in &[20, 0, method] {
+                fields.extend_from_slice(&n.to_le_bytes());
+            }
+            fields.extend_from_slice(&[0; 4]);
+            for n in &[crc32(data), raw.len() as u32, data.len() as u32] {
+                fields.extend_from_slice(&n.to_le_bytes());
+            }
+            fields.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            fields.extend_from_slice(&[0; 2]);
+
+            central.extend_from_slice(&[0x50, 0x4b, 0x01, 0x02, 20, 0]);
+            central.extend_from_slice(&fields);
+            central.extend_from_slice(&[0; 10]);
+            central.extend_from_slice(&(zip.len() as u32).to_le_bytes());
+            central.extend_from_slice(name.as_bytes());
+
+            zip.extend_from_slice(&[0x50, 0x4b, 0x03, 0x04]);
+            zip.extend_from_slice(&fields);
+            zip.extend_from_slice(name.as_bytes());
+            zip.extend_from_slice(raw);
+        }
+
+        let offset = zip.len() as u32;
+        zip.extend_from_slice(&central);
+        zip.extend_from_slice(&[0x50, 0x4b, 0x05, 0x06, 0, 0, 0, 0]);
+        zip.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        zip.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        zip.extend_from_slice(&(central.len() as u32).to_le_bytes());
+        zip.extend_from_slice(&offset.to_le_bytes());
+        zip.extend_from_slice(&[0, 0]);
+        zip
+    }
+
+    // Compressed with zlib, level 9
+    const FIXED: [u8; 11] = [203, 72, 205, 201, 201, 87, 200, 64, 144, 92, 0];
+    const DYNAMIC: [u8; 30] = [
+        5, 193, 193, 17, 0, 32, 12, 2, 193, 255, 85, 3, 138, 76, 210, 127, 99, 238, 10, 19, 22, 151, 243, 184, 37, 75, 195, 24, 75, 124,
+    ];
+    const SQUARES: &[u8] = b"0\n1\n4\n9\n16\n25\n36\n49\n64\n81\n100\n";
+
+    #[test]
+    fn inflate_blocks() {
+        assert_eq!(inflate(&FIXED).unwrap(), b"hello hello hello\n");
+        assert_eq!(inflate(&DYNAMIC).unwrap(), SQUARES);
+        assert_eq!(inflate(&[0, 2, 0, 0xfd, 0xff, b'o', b'k', 1, 1, 0, 0xfe, 0xff, b'\n']).unwrap(), b"ok\n");
+
+        assert!(inflate(&FIXED[..5]).is_err());
+        assert!(inflate(&[1, 1, 0, 0xff, 0xff, b'a']).is_err());
+        assert!(inflate(&[7]).is_err());
+    }
+
+    #[test]
+    fn read_archive() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+
+        let zip = archive(&[("samples/", 0, b"", b""), ("1.in", 8, &DYNAMIC, SQUARES), ("1.ans", 0, b"42\n", b"42\n")]);
+        let entries = read(&zip).unwrap();
+        assert_eq!(
+            entries,
+            vec![Entry { name: "1.in".to_string(), data: SQUARES.to_vec() }, Entry { name: "1.ans".to_string(), data: b"42\n".to_vec() }]
+        );
+
+        assert!(read(&archive(&[("1.ans", 0, b"42\n", b"43\n")])).unwrap_err().contains("corrupt"));
+        assert!(read(&archive(&[("1.in", 12, b"42\n", b"42\n")])).unwrap_err().contains("unsupported"));
+        assert!(read(&zip[..zip.len() - 1]).is_err());
+        assert!(read(b"PK").is_err());
+    }
+}